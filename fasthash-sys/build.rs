@@ -18,6 +18,8 @@ fn generate_binding(out_file: &Path) {
                    })
         .header("src/fasthash.hpp")
         .no_unstable_rust()
+        .use_core()
+        .ctypes_prefix("::core::ffi")
         .generate_inline_functions(true)
         .disable_name_namespacing()
         .hide_type(".*PCCP.*")
@@ -56,7 +58,7 @@ fn main() {
         .flag("-Wno-unknown-attributes")
         .file("src/fasthash.cpp")
         .file("src/smhasher/City.cpp")
-        .file("src/smhasher/farmhash-c.c")
+        .file("src/farmhash.c")
         .file("src/smhasher/lookup3.cpp")
         .file("src/smhasher/mum.cc")
         .file("src/smhasher/metrohash64.cpp")
@@ -77,6 +79,20 @@ fn main() {
 
     gcc_config.compile("libfasthash.a");
 
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+
+    if target_arch == "x86" || target_arch == "x86_64" {
+        gcc::Build::new()
+            .file("src/farmhash_portable.c")
+            .compile("libfarmhash_portable.a");
+
+        gcc::Build::new()
+            .flag("-msse4.2")
+            .flag("-maes")
+            .file("src/farmhash_sse42.c")
+            .compile("libfarmhash_sse42.a");
+    }
+
     let out_dir = env::var("OUT_DIR").unwrap();
     let out_file = Path::new(&out_dir).join("src/fasthash.rs");
 
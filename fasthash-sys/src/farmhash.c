@@ -0,0 +1,6 @@
+/* `farmhash-c.c` built with the flags of the library, reporting the variants they select. */
+#include "smhasher/farmhash-c.c"
+
+int farmhash_is_portable(void) {
+  return !(CAN_USE_SSE41 || CAN_USE_SSE42 || CAN_USE_AESNI);
+}
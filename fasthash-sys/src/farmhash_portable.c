@@ -0,0 +1,4 @@
+/* `farmhash-c.c` built without any target specific instruction. */
+#define FARMHASH_VARIANT(name) name##_portable
+
+#include "farmhash_variant.h"
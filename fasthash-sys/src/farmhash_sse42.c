@@ -0,0 +1,4 @@
+/* `farmhash-c.c` built with the SSE4.2 and AES-NI instructions. */
+#define FARMHASH_VARIANT(name) name##_sse42
+
+#include "farmhash_variant.h"
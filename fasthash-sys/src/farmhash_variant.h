@@ -0,0 +1,50 @@
+/*
+ * Compiles `farmhash-c.c` again with every public symbol renamed by
+ * `FARMHASH_VARIANT(name)`, so a copy built with different target flags
+ * can be linked next to the default one.
+ *
+ * This includes the internal variants, e.g. `farmhash64_na`, which are not static
+ * either; the `_nt`, `_sa`, `_su` and `_te` ones only exist with SSE4.x.
+ */
+#ifndef FARMHASH_VARIANT
+#error "FARMHASH_VARIANT(name) must be defined"
+#endif
+
+#define debug_tweak128 FARMHASH_VARIANT(debug_tweak128)
+#define farmhash FARMHASH_VARIANT(farmhash)
+#define farmhash128 FARMHASH_VARIANT(farmhash128)
+#define farmhash128_cc_city_with_seed FARMHASH_VARIANT(farmhash128_cc_city_with_seed)
+#define farmhash128_with_seed FARMHASH_VARIANT(farmhash128_with_seed)
+#define farmhash32 FARMHASH_VARIANT(farmhash32)
+#define farmhash32_cc FARMHASH_VARIANT(farmhash32_cc)
+#define farmhash32_cc_with_seed FARMHASH_VARIANT(farmhash32_cc_with_seed)
+#define farmhash32_mk FARMHASH_VARIANT(farmhash32_mk)
+#define farmhash32_mk_with_seed FARMHASH_VARIANT(farmhash32_mk_with_seed)
+#define farmhash32_nt FARMHASH_VARIANT(farmhash32_nt)
+#define farmhash32_nt_with_seed FARMHASH_VARIANT(farmhash32_nt_with_seed)
+#define farmhash32_sa FARMHASH_VARIANT(farmhash32_sa)
+#define farmhash32_sa_with_seed FARMHASH_VARIANT(farmhash32_sa_with_seed)
+#define farmhash32_su FARMHASH_VARIANT(farmhash32_su)
+#define farmhash32_su_with_seed FARMHASH_VARIANT(farmhash32_su_with_seed)
+#define farmhash32_with_seed FARMHASH_VARIANT(farmhash32_with_seed)
+#define farmhash64 FARMHASH_VARIANT(farmhash64)
+#define farmhash64_na FARMHASH_VARIANT(farmhash64_na)
+#define farmhash64_na_with_seed FARMHASH_VARIANT(farmhash64_na_with_seed)
+#define farmhash64_na_with_seeds FARMHASH_VARIANT(farmhash64_na_with_seeds)
+#define farmhash64_te FARMHASH_VARIANT(farmhash64_te)
+#define farmhash64_te_with_seed FARMHASH_VARIANT(farmhash64_te_with_seed)
+#define farmhash64_te_with_seeds FARMHASH_VARIANT(farmhash64_te_with_seeds)
+#define farmhash64_uo FARMHASH_VARIANT(farmhash64_uo)
+#define farmhash64_uo_with_seed FARMHASH_VARIANT(farmhash64_uo_with_seed)
+#define farmhash64_uo_with_seeds FARMHASH_VARIANT(farmhash64_uo_with_seeds)
+#define farmhash64_with_seed FARMHASH_VARIANT(farmhash64_with_seed)
+#define farmhash64_with_seeds FARMHASH_VARIANT(farmhash64_with_seeds)
+#define farmhash64_xo FARMHASH_VARIANT(farmhash64_xo)
+#define farmhash64_xo_with_seed FARMHASH_VARIANT(farmhash64_xo_with_seed)
+#define farmhash64_xo_with_seeds FARMHASH_VARIANT(farmhash64_xo_with_seeds)
+#define farmhash_cc_fingerprint128 FARMHASH_VARIANT(farmhash_cc_fingerprint128)
+#define farmhash_fingerprint128 FARMHASH_VARIANT(farmhash_fingerprint128)
+#define farmhash_fingerprint32 FARMHASH_VARIANT(farmhash_fingerprint32)
+#define farmhash_fingerprint64 FARMHASH_VARIANT(farmhash_fingerprint64)
+
+#include "smhasher/farmhash-c.c"
@@ -1,3 +1,6 @@
+#include <new>
+#include <string.h>
+
 #include "fasthash.hpp"
 
 uint64_t farmhash_fingerprint_uint128(uint128_c_t x) {
@@ -21,10 +24,74 @@ void SpookyHasherHash(
     SpookyHash::Hash128(message, length, hash1, hash2);
 }
 
-void *SpookyHasherNew() { return new SpookyHash(); }
+// `nothrow`, so a failed allocation returns `NULL` instead of unwinding into Rust
+void *SpookyHasherNew() { return new (std::nothrow) SpookyHash(); }
 
 void SpookyHasherFree(void *h) { delete ((SpookyHash *) h); }
 
+void *SpookyHasherClone(void *h) { return new (std::nothrow) SpookyHash(*((SpookyHash *) h)); }
+
+void SpookyHasherZero(void *h)
+{
+    // volatile stores, so the compiler can't drop them before the `delete`
+    volatile unsigned char *p = (volatile unsigned char *) h;
+
+    for (size_t i = 0; i < sizeof(SpookyHash); i++) {
+        p[i] = 0;
+    }
+}
+
+// Mirrors the private members of `SpookyHash`, to snapshot and validate its state
+struct SpookyHashState {
+    uint64 data[2 * 12]; // unhashed data, for partial messages
+    uint64 state[12];    // internal state of the hash
+    size_t length;       // total length of the input so far
+    uint8 remainder;     // length of unhashed data stashed in data
+};
+
+static_assert(sizeof(SpookyHashState) == sizeof(SpookyHash), "unexpected SpookyHash layout");
+
+static const size_t SpookyBlockSize = 12 * 8;
+
+size_t SpookyHasherStateSize() { return sizeof(SpookyHashState); }
+
+void SpookyHasherSnapshot(const void *h, void *out)
+{
+    const SpookyHashState *s = (const SpookyHashState *) h;
+    SpookyHashState *o = (SpookyHashState *) out;
+
+    // copy by member, so the padding bytes of the snapshot are zero rather than undefined
+    memset(o, 0, sizeof(SpookyHashState));
+    memcpy(o->data, s->data, sizeof(s->data));
+    memcpy(o->state, s->state, sizeof(s->state));
+    o->length = s->length;
+    o->remainder = s->remainder;
+}
+
+bool SpookyHasherRestore(void *h, const void *snapshot, size_t length)
+{
+    SpookyHashState s;
+
+    if (length != sizeof(SpookyHashState)) {
+        return false;
+    }
+
+    memcpy(&s, snapshot, sizeof(SpookyHashState));
+
+    // the buffer holds less than two blocks, and a short input is only buffered;
+    // after a long input, a short update may buffer up to two blocks less a byte,
+    // whatever the total length, so any other remainder is a corrupted state,
+    // which may overflow the buffer
+    if (s.remainder >= 2 * SpookyBlockSize ||
+        (s.length < 2 * SpookyBlockSize && s.remainder != s.length)) {
+        return false;
+    }
+
+    memcpy(h, &s, sizeof(SpookyHashState));
+
+    return true;
+}
+
 void SpookyHasherInit(
     void *h,
     uint64 seed1,       // any 64-bit value will do, including 0
@@ -46,5 +113,13 @@ void SpookyHasherFinal(
     uint64 *hash1,    // out only: first 64 bits of hash value.
     uint64 *hash2)    // out only: second 64 bits of hash value.
 {
+    const SpookyHashState *s = (const SpookyHashState *) h;
+
+    // `Final` hashes a message shorter than two blocks in one go with `Short`,
+    // which reads its seed from `hash1` and `hash2`; until a block is mixed,
+    // the first two words of the state still hold the seed of `Init`
+    *hash1 = s->state[0];
+    *hash2 = s->state[1];
+
     ((SpookyHash *) h)->Final(hash1, hash2);
 }
\ No newline at end of file
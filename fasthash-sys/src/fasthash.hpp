@@ -9,6 +9,16 @@
 #include "smhasher/t1ha.h"
 #include "smhasher/xxhash.h"
 
+extern "C" {
+// `farmhash64` built without target specific instructions, and with SSE4.2 and AES-NI
+uint64_t farmhash64_portable(const char *s, size_t len);
+
+uint64_t farmhash64_sse42(const char *s, size_t len);
+
+// Whether `farmhash-c.c` was built without its SSE4.1, SSE4.2 and AES-NI variants
+int farmhash_is_portable(void);
+}
+
 uint64_t farmhash_fingerprint_uint128(uint128_c_t x);
 
 uint64_t farmhash_fingerprint_uint64(uint64_t x);
@@ -27,6 +37,16 @@ void *SpookyHasherNew();
 
 void SpookyHasherFree(void *h);
 
+void *SpookyHasherClone(void *h);
+
+void SpookyHasherZero(void *h);
+
+size_t SpookyHasherStateSize();
+
+void SpookyHasherSnapshot(const void *h, void *out);
+
+bool SpookyHasherRestore(void *h, const void *snapshot, size_t length);
+
 void SpookyHasherInit(
     void *h,
     uint64 seed1,  // any 64-bit value will do, including 0
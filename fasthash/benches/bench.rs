@@ -30,7 +30,7 @@ fn bench_fasthash<H: FastHash>(b: &mut Bencher, size: usize) {
         let n = test::black_box(ITERATERS);
 
         (0..n).fold(0, |_, _| {
-            H::hash(&key);
+            test::black_box(H::hash(&key));
             0
         })
     });
@@ -68,4 +68,75 @@ fn bench_buf_hasher<H: BufHasher>(b: &mut Bencher, size: usize) {
     });
 }
 
+/// A `Hasher` forwarding only `write`, so the integers go through the default `write_*`.
+struct DefaultWriteHasher<H>(H);
+
+impl<H: Hasher> Hasher for DefaultWriteHasher<H> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes)
+    }
+}
+
+#[inline]
+fn bench_write_u64<H: Hasher, F: Fn() -> H>(b: &mut Bencher, new: F) {
+    b.bytes = (8 * ITERATERS) as u64;
+    b.iter(|| {
+        let mut h = new();
+
+        for i in 0..test::black_box(ITERATERS) as u64 {
+            h.write_u64(i);
+        }
+
+        h.finish()
+    });
+}
+
+#[bench]
+fn bench_farm_hasher64_write_u64(b: &mut Bencher) {
+    bench_write_u64(b, FarmHasher::new);
+}
+
+#[bench]
+fn bench_farm_hasher64_write_u64_default(b: &mut Bencher) {
+    bench_write_u64(b, || DefaultWriteHasher(FarmHasher::new()));
+}
+
+fn gen_records(count: usize, size: usize) -> Vec<Vec<u8>> {
+    (0..count).map(|_| gen_key(size)).collect()
+}
+
+#[bench]
+fn bench_farm_fingerprint64_loop(b: &mut Bencher) {
+    let records = gen_records(ITERATERS, 32);
+    let mut out = vec![0; records.len()];
+
+    b.bytes = (32 * ITERATERS) as u64;
+    b.iter(|| {
+        for (record, h) in records.iter().zip(out.iter_mut()) {
+            *h = farm::fingerprint64(record);
+        }
+
+        test::black_box(&out);
+    });
+}
+
+#[bench]
+fn bench_farm_fingerprint64_many(b: &mut Bencher) {
+    let records = gen_records(ITERATERS, 32);
+    let mut out = vec![0; records.len()];
+
+    b.bytes = (32 * ITERATERS) as u64;
+    b.iter(|| {
+        farm::fingerprint64_many(&records, &mut out);
+
+        test::black_box(&out);
+    });
+}
+
 include!(concat!(env!("OUT_DIR"), "/benches.rs"));
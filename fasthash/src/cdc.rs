@@ -0,0 +1,219 @@
+//! Content-defined chunking.
+//!
+//! A `Chunker` slides the rsync rolling checksum over the data, and cuts a chunk
+//! wherever the mixed checksum of the last bytes matches a mask, like FastCDC.
+//! The boundaries only depend on the nearby content, so inserting or removing bytes
+//! only changes the chunks around the edit, and identical data always yields
+//! identical chunks.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::cdc::Chunker;
+//!
+//! let data = (0..100_000_u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+//!     .collect::<Vec<u8>>();
+//! let chunker = Chunker::new(512, 2048, 8192);
+//!
+//! let mut offset = 0;
+//!
+//! for (off, len) in chunker.chunks(&data) {
+//!     assert_eq!(off, offset);
+//!     assert!(len <= 8192);
+//!
+//!     offset += len;
+//! }
+//!
+//! assert_eq!(offset, data.len());
+//! ```
+//!
+use std::cmp;
+
+use rsync::RollingChecksum;
+
+/// The number of bytes hashed by the rolling checksum at each position.
+const WINDOW_SIZE: usize = 48;
+
+/// Splits data into content-defined chunks between a minimum and a maximum size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chunker {
+    min_size: usize,
+    max_size: usize,
+    mask: u64,
+}
+
+impl Chunker {
+    /// Constructs a new `Chunker` with the given chunk sizes.
+    ///
+    /// The boundary mask is `avg_size.next_power_of_two() - 1`, so a boundary is found
+    /// every `avg_size` bytes or so after the minimum size.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < min_size <= avg_size <= max_size`.
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        assert!(0 < min_size && min_size <= avg_size && avg_size <= max_size,
+                "chunk sizes must satisfy 0 < min_size <= avg_size <= max_size");
+
+        Chunker {
+            min_size: min_size,
+            max_size: max_size,
+            mask: avg_size.next_power_of_two() as u64 - 1,
+        }
+    }
+
+    /// Returns the minimum size of a chunk, except the last one.
+    #[inline]
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// Returns the maximum size of a chunk.
+    #[inline]
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the mask a boundary checksum must match.
+    #[inline]
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// Returns an iterator over the `(offset, len)` chunks of the data.
+    #[inline]
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> Chunks<'a> {
+        Chunks {
+            chunker: *self,
+            data: data,
+            pos: 0,
+        }
+    }
+
+    #[inline]
+    fn is_boundary(&self, sum: &RollingChecksum) -> bool {
+        // the low bits of the checksum are a plain byte sum, mix them before masking
+        let h = (sum.value() as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32;
+
+        h & self.mask == 0
+    }
+}
+
+/// An iterator over the `(offset, len)` chunks of some data, created by `Chunker::chunks`.
+#[derive(Clone, Debug)]
+pub struct Chunks<'a> {
+    chunker: Chunker,
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let start = self.pos;
+        let remaining = self.data.len() - start;
+
+        if remaining == 0 {
+            return None;
+        }
+
+        if remaining <= self.chunker.min_size {
+            self.pos = self.data.len();
+
+            return Some((start, remaining));
+        }
+
+        let limit = start + cmp::min(self.chunker.max_size, remaining);
+        let window = cmp::min(WINDOW_SIZE, self.chunker.min_size);
+        let mut end = start + self.chunker.min_size;
+        let mut sum = RollingChecksum::new(&self.data[end - window..end]);
+
+        while end < limit && !self.chunker.is_boundary(&sum) {
+            sum.roll(self.data[end - window], self.data[end]);
+            end += 1;
+        }
+
+        self.pos = end;
+
+        Some((start, end - start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_data(len: usize, mut seed: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunker() {
+        let data = random_data(1 << 18, 123);
+        let chunker = Chunker::new(1024, 4096, 16384);
+
+        assert_eq!(chunker.min_size(), 1024);
+        assert_eq!(chunker.max_size(), 16384);
+        assert_eq!(chunker.mask(), 4095);
+
+        let chunks = chunker.chunks(&data).collect::<Vec<_>>();
+
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks, chunker.chunks(&data).collect::<Vec<_>>());
+        assert_eq!(chunks, chunker.chunks(&data.clone()).collect::<Vec<_>>());
+
+        let mut offset = 0;
+
+        for (i, &(off, len)) in chunks.iter().enumerate() {
+            assert_eq!(off, offset);
+            assert!(len <= chunker.max_size());
+
+            if i + 1 < chunks.len() {
+                assert!(len >= chunker.min_size());
+            }
+
+            offset += len;
+        }
+
+        assert_eq!(offset, data.len());
+
+        // the boundaries only depend on the content, not on the preceding chunks
+        let (off, _) = chunks[chunks.len() / 2];
+        let tail = chunker.chunks(&data[off..])
+            .map(|(o, len)| (o + off, len))
+            .collect::<Vec<_>>();
+
+        assert_eq!(&chunks[chunks.len() / 2..], &tail[..]);
+    }
+
+    #[test]
+    fn test_chunker_short_input() {
+        let chunker = Chunker::new(16, 32, 64);
+
+        assert_eq!(chunker.chunks(b"").next(), None);
+        assert_eq!(chunker.chunks(b"hello").collect::<Vec<_>>(), vec![(0, 5)]);
+        assert_eq!(chunker.chunks(&[1; 16]).collect::<Vec<_>>(), vec![(0, 16)]);
+
+        // a boundary is unlikely with a large mask, the whole input is a single chunk
+        let data = random_data(200, 7);
+
+        assert_eq!(Chunker::new(16, 1 << 20, 1 << 20).chunks(&data).collect::<Vec<_>>(),
+                   vec![(0, 200)]);
+        assert_eq!(Chunker::new(16, 1 << 20, 1 << 20).chunks(&data[..100]).count(), 1);
+        assert!(Chunker::new(16, 64, 64).chunks(&data).all(|(_, len)| len <= 64));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunker_invalid_sizes() {
+        Chunker::new(4096, 1024, 16384);
+    }
+}
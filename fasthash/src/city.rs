@@ -122,11 +122,11 @@
 //!
 use std::mem;
 
-use extprim::u128::u128;
-
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `CityHash` 32-bit hash functions
 pub struct CityHash32 {}
@@ -136,7 +136,7 @@ impl FastHash for CityHash32 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::CityHash32WithSeed(bytes.as_ref().as_ptr() as *const i8,
                                     bytes.as_ref().len(),
@@ -154,7 +154,7 @@ impl CityHash64 {
     /// Hash functions for a byte array.
     /// For convenience, seeds are also hashed into the result.
     #[inline]
-    pub fn hash_with_seeds<T: AsRef<[u8]>>(bytes: &T, seed0: u64, seed1: u64) -> u64 {
+    pub fn hash_with_seeds<T: AsRef<[u8]>>(bytes: T, seed0: u64, seed1: u64) -> u64 {
         unsafe {
             ffi::CityHash64WithSeeds(bytes.as_ref().as_ptr() as *const i8,
                                      bytes.as_ref().len(),
@@ -169,14 +169,14 @@ impl FastHash for CityHash64 {
     type Seed = u64;
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u64 {
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u64 {
         unsafe { ffi::CityHash64(bytes.as_ref().as_ptr() as *const i8, bytes.as_ref().len()) }
     }
 
     /// Hash functions for a byte array.
     /// For convenience, a seed is also hashed into the result.
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::CityHash64WithSeed(bytes.as_ref().as_ptr() as *const i8,
                                     bytes.as_ref().len(),
@@ -187,6 +187,13 @@ impl FastHash for CityHash64 {
 
 impl_hasher!(CityHasher64, CityHash64);
 
+// The 128-bit hashes `transmute` between `uint128_c_t` and `u128`, which is only correct
+// if the struct is exactly the low 64 bits followed by the high 64 bits; a change of the
+// C struct would silently corrupt every 128-bit hash, so fail the build instead.
+const _: () = assert!(mem::size_of::<ffi::uint128_c_t>() == mem::size_of::<u128>());
+const _: () = assert!(mem::offset_of!(ffi::uint128_c_t, a) == 0);
+const _: () = assert!(mem::offset_of!(ffi::uint128_c_t, b) == 8);
+
 /// `CityHash` 128-bit hash functions
 pub struct CityHash128 {}
 
@@ -195,7 +202,7 @@ impl FastHash for CityHash128 {
     type Seed = u128;
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u128 {
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u128 {
         unsafe {
             mem::transmute(ffi::CityHash128(bytes.as_ref().as_ptr() as *const i8,
                                             bytes.as_ref().len()))
@@ -203,7 +210,7 @@ impl FastHash for CityHash128 {
     }
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u128) -> u128 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
         unsafe {
             mem::transmute(ffi::CityHash128WithSeed(bytes.as_ref().as_ptr() as *const i8,
                                                     bytes.as_ref().len(),
@@ -224,7 +231,7 @@ impl FastHash for CityHashCrc128 {
     type Seed = u128;
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u128 {
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u128 {
         unsafe {
             mem::transmute(ffi::CityHashCrc128(bytes.as_ref().as_ptr() as *const i8,
                                                bytes.as_ref().len()))
@@ -232,7 +239,7 @@ impl FastHash for CityHashCrc128 {
     }
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u128) -> u128 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
         unsafe {
             mem::transmute(ffi::CityHashCrc128WithSeed(bytes.as_ref().as_ptr() as *const i8,
                                                        bytes.as_ref().len(),
@@ -246,41 +253,50 @@ impl_hasher_ext!(CityHasherCrc128, CityHashCrc128);
 
 /// `CityHash` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     CityHash32::hash(v)
 }
 
 /// `CityHash` 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     CityHash32::hash_with_seed(v, seed)
 }
 
 /// `CityHash` 64-bit hash functions for a byte array.
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     CityHash64::hash(v)
 }
 
 /// `CityHash` 64-bit hash function for a byte array.
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     CityHash64::hash_with_seed(v, seed)
 }
 
+/// `CityHash` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 /// `CityHash` 64-bit hash function for a byte array.
 /// For convenience, two seeds are also hashed into the result.
 #[inline]
-pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: &T, seed0: u64, seed1: u64) -> u64 {
+pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
     CityHash64::hash_with_seeds(v, seed0, seed1)
 }
 
 /// `CityHash` 128-bit hash function for a byte array.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     CityHash128::hash(v)
 }
 
@@ -288,7 +304,7 @@ pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
 /// For convenience, a 128-bit seed is also hashed into the result.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u128) -> u128 {
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
     CityHash128::hash_with_seed(v, seed)
 }
 
@@ -296,7 +312,7 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u128) -> u128 {
 /// That require SSE4.2 instructions to be available.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     CityHashCrc128::hash(v)
 }
 
@@ -305,7 +321,7 @@ pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
 /// That require SSE4.2 instructions to be available.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u128) -> u128 {
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
     CityHashCrc128::hash_with_seed(v, seed)
 }
 
@@ -313,8 +329,6 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u128) -> u128 {
 mod tests {
     use std::hash::Hasher;
 
-    use extprim::u128::u128;
-
     use hasher::{FastHash, FastHasher, HasherExt};
     use super::*;
 
@@ -351,44 +365,53 @@ mod tests {
         assert_eq!(h.finish(), 16622738483577116029);
     }
 
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_uint128_c_layout() {
+        let v: u128 = unsafe { mem::transmute(ffi::uint128_c_t { a: 456, b: 123 }) };
+
+        assert_eq!(v, (123_u128 << 64) | 456);
+        assert_eq!(mem::size_of::<ffi::uint128_c_t>(), 16);
+    }
+
     #[test]
     fn test_cityhash128() {
         assert_eq!(CityHash128::hash(b"hello"),
-                   u128::from_parts(17404193039403234796, 13523890104784088047));
-        assert_eq!(CityHash128::hash_with_seed(b"hello", u128::new(123)),
-                   u128::from_parts(10365139276371188890, 13112352013023211873));
+                   (17404193039403234796_u128 << 64) | 13523890104784088047);
+        assert_eq!(CityHash128::hash_with_seed(b"hello", 123),
+                   (10365139276371188890_u128 << 64) | 13112352013023211873);
         assert_eq!(CityHash128::hash(b"helloworld"),
-                   u128::from_parts(7450567370945444069, 787832070172609324));
+                   (7450567370945444069_u128 << 64) | 787832070172609324);
 
         let mut h = CityHasher128::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(17404193039403234796, 13523890104784088047));
+                   (17404193039403234796_u128 << 64) | 13523890104784088047);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(7450567370945444069, 787832070172609324));
+                   (7450567370945444069_u128 << 64) | 787832070172609324);
     }
 
     #[cfg(feature = "sse42")]
     #[test]
     fn test_cityhash128crc() {
         assert_eq!(CityHashCrc128::hash(b"hello"),
-                   u128::from_parts(17404193039403234796, 13523890104784088047));
-        assert_eq!(CityHashCrc128::hash_with_seed(b"hello", u128::new(123)),
-                   u128::from_parts(10365139276371188890, 13112352013023211873));
+                   (17404193039403234796_u128 << 64) | 13523890104784088047);
+        assert_eq!(CityHashCrc128::hash_with_seed(b"hello", 123),
+                   (10365139276371188890_u128 << 64) | 13112352013023211873);
         assert_eq!(CityHashCrc128::hash(b"helloworld"),
-                   u128::from_parts(7450567370945444069, 787832070172609324));
+                   (7450567370945444069_u128 << 64) | 787832070172609324);
 
         let mut h = CityHasherCrc128::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(17404193039403234796, 13523890104784088047));
+                   (17404193039403234796_u128 << 64) | 13523890104784088047);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(7450567370945444069, 787832070172609324));
+                   (7450567370945444069_u128 << 64) | 787832070172609324);
     }
 }
@@ -0,0 +1,198 @@
+//! Runtime selection of a hash algorithm.
+//!
+//! `Algorithm` names a hash function chosen at runtime, e.g. from a `--algo spooky64`
+//! command line flag, and routes to the matching module function.
+//! An algorithm only produces its own width: asking a 128-bit algorithm for
+//! a 64-bit hash, or the opposite, is an error rather than a silent truncation.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::dispatch::Algorithm;
+//! use fasthash::spooky;
+//!
+//! let algo: Algorithm = "spooky64".parse().unwrap();
+//!
+//! assert_eq!(algo, Algorithm::Spooky64);
+//! assert_eq!(algo.hash64(b"hello"), Ok(spooky::hash64(b"hello")));
+//! assert!(algo.hash128(b"hello").is_err());
+//! ```
+//!
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use farm;
+use spooky;
+
+/// A hash algorithm selectable at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// `farm::hash64`
+    Farm64,
+    /// `farm::hash128`
+    Farm128,
+    /// `spooky::hash64`
+    Spooky64,
+    /// `spooky::hash128`
+    Spooky128,
+}
+
+impl Algorithm {
+    /// All the algorithms.
+    pub const ALL: [Algorithm; 4] = [Algorithm::Farm64,
+                                     Algorithm::Farm128,
+                                     Algorithm::Spooky64,
+                                     Algorithm::Spooky128];
+
+    /// Returns the name of the algorithm, as parsed by `from_str`.
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Farm64 => "farm64",
+            Algorithm::Farm128 => "farm128",
+            Algorithm::Spooky64 => "spooky64",
+            Algorithm::Spooky128 => "spooky128",
+        }
+    }
+
+    /// Returns the width of the hash values in bits.
+    #[inline]
+    pub fn bits(self) -> u32 {
+        match self {
+            Algorithm::Farm64 | Algorithm::Spooky64 => 64,
+            Algorithm::Farm128 | Algorithm::Spooky128 => 128,
+        }
+    }
+
+    /// Hash the byte array with a 64-bit algorithm.
+    ///
+    /// Returns an `UnsupportedWidth` error for a 128-bit algorithm.
+    #[inline]
+    pub fn hash64(self, bytes: &[u8]) -> Result<u64, UnsupportedWidth> {
+        match self {
+            Algorithm::Farm64 => Ok(farm::hash64(bytes)),
+            Algorithm::Spooky64 => Ok(spooky::hash64(bytes)),
+            _ => Err(self.unsupported(64)),
+        }
+    }
+
+    /// Hash the byte array with a 128-bit algorithm.
+    ///
+    /// Returns an `UnsupportedWidth` error for a 64-bit algorithm.
+    #[inline]
+    pub fn hash128(self, bytes: &[u8]) -> Result<u128, UnsupportedWidth> {
+        match self {
+            Algorithm::Farm128 => Ok(farm::hash128(bytes)),
+            Algorithm::Spooky128 => Ok(spooky::hash128(bytes)),
+            _ => Err(self.unsupported(128)),
+        }
+    }
+
+    fn unsupported(self, bits: u32) -> UnsupportedWidth {
+        UnsupportedWidth {
+            algorithm: self,
+            bits: bits,
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ParseAlgorithmError;
+
+    /// Parses the name of an algorithm, e.g. `"farm64"`, ignoring the ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Algorithm::ALL
+            .iter()
+            .cloned()
+            .find(|algo| algo.name().eq_ignore_ascii_case(s))
+            .ok_or(ParseAlgorithmError)
+    }
+}
+
+/// The error of hashing with an algorithm which doesn't produce the requested width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedWidth {
+    /// The algorithm.
+    pub algorithm: Algorithm,
+    /// The requested width in bits.
+    pub bits: u32,
+}
+
+impl fmt::Display for UnsupportedWidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{} produces {}-bit hashes, not {}-bit",
+               self.algorithm,
+               self.algorithm.bits(),
+               self.bits)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for UnsupportedWidth {}
+
+/// The error of parsing an unknown algorithm name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseAlgorithmError;
+
+impl fmt::Display for ParseAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown hash algorithm")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseAlgorithmError {}
+
+#[cfg(test)]
+mod tests {
+    use farm;
+    use spooky;
+    use super::*;
+
+    #[test]
+    fn test_parse_algorithm() {
+        assert_eq!("farm64".parse(), Ok(Algorithm::Farm64));
+        assert_eq!("farm128".parse(), Ok(Algorithm::Farm128));
+        assert_eq!("spooky64".parse(), Ok(Algorithm::Spooky64));
+        assert_eq!("Spooky128".parse(), Ok(Algorithm::Spooky128));
+        assert_eq!("md5".parse::<Algorithm>(), Err(ParseAlgorithmError));
+        assert_eq!("".parse::<Algorithm>(), Err(ParseAlgorithmError));
+
+        for &algo in &Algorithm::ALL {
+            assert_eq!(algo.to_string().parse(), Ok(algo));
+        }
+    }
+
+    #[test]
+    fn test_algorithm_routing() {
+        assert_eq!(Algorithm::Farm64.hash64(b"hello"), Ok(farm::hash64(b"hello")));
+        assert_eq!(Algorithm::Farm128.hash128(b"hello"), Ok(farm::hash128(b"hello")));
+        assert_eq!(Algorithm::Spooky64.hash64(b"hello"), Ok(6105954949053820864));
+        assert_eq!(Algorithm::Spooky128.hash128(b"hello"), Ok(spooky::hash128(b"hello")));
+    }
+
+    #[test]
+    fn test_unsupported_width() {
+        assert_eq!(Algorithm::Farm128.hash64(b"hello"),
+                   Err(UnsupportedWidth {
+                       algorithm: Algorithm::Farm128,
+                       bits: 64,
+                   }));
+        assert_eq!(Algorithm::Spooky64.hash128(b"hello"),
+                   Err(UnsupportedWidth {
+                       algorithm: Algorithm::Spooky64,
+                       bits: 128,
+                   }));
+        assert_eq!(Algorithm::Farm64.hash128(b"").unwrap_err().to_string(),
+                   "farm64 produces 64-bit hashes, not 128-bit");
+    }
+}
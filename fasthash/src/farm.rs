@@ -100,6 +100,10 @@
 //! 3) The techniques described in dev/INSTRUCTIONS to let hash function
 //! developers regenerate src/*.cc from dev/* are hacky and not so portable.
 //!
+//! `FarmHash` has no streaming API, so the `FarmHasher*` hashers buffer every `write`
+//! and hash the whole input on `finish`. They agree with the one-shot functions only
+//! because of this buffering, and their memory use grows with the input.
+//!
 //! # Example
 //!
 //! ```
@@ -116,17 +120,54 @@
 //! let h = farm::hash64(b"hello world\xff");
 //!
 //! assert_eq!(h, hash(&"hello world"));
+//!
+//! // the `Hash` implementation of `str` appends a `0xff` byte, `hash64_str` doesn't
+//! assert_eq!(farm::hash64_hashlike("hello world"), h);
+//! assert_eq!(farm::hash64_str("hello world"), farm::hash64(b"hello world"));
 //! ```
 //!
+use std::fmt;
+use std::str;
 use std::mem;
+use std::num::ParseIntError;
+use std::ops::Deref;
+#[cfg(feature = "alloc")]
+use std::hash::{BuildHasher, Hash, Hasher};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "mmap")]
+use std::fs::File;
+#[cfg(feature = "mmap")]
+use std::io::{self, Read};
+#[cfg(feature = "mmap")]
+use std::path::Path;
+
+#[cfg(feature = "std")]
+use rand::{OsRng, Rng};
+#[cfg(all(feature = "mmap", unix))]
+use memmap2::Advice;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
 
-use extprim::u128::u128;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use ffi;
 
-use hasher::{Fingerprint, FastHash, FastHasher};
+use hasher::{Fingerprint, FastHash, StableHash, u128_from_parts, u128_to_parts};
+#[cfg(feature = "alloc")]
+use hasher::{FastHasher, HasherExt};
 
 /// `FarmHash` 32-bit hash functions
+///
+/// `FarmHash` has no streaming C API, so `FarmHasher32` buffers the written bytes
+/// and hashes them once in `finish`; writes are amortized O(1) and `finish` is O(n),
+/// and the result always agrees with `FarmHash32::hash` over the concatenated input,
+/// whatever the split points and however large the input is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct FarmHash32 {}
 
 impl FastHash for FarmHash32 {
@@ -134,12 +175,12 @@ impl FastHash for FarmHash32 {
     type Seed = u32;
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u32 {
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u32 {
         unsafe { ffi::farmhash32(bytes.as_ref().as_ptr() as *const i8, bytes.as_ref().len()) }
     }
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::farmhash32_with_seed(bytes.as_ref().as_ptr() as *const i8,
                                       bytes.as_ref().len(),
@@ -151,13 +192,15 @@ impl FastHash for FarmHash32 {
 impl_hasher!(FarmHasher32, FarmHash32);
 
 /// `FarmHash` 64-bit hash functions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct FarmHash64 {}
 
 impl FarmHash64 {
     /// Hash functions for a byte array.
     /// For convenience, seeds are also hashed into the result.
     #[inline]
-    pub fn hash_with_seeds<T: AsRef<[u8]>>(bytes: &T, seed0: u64, seed1: u64) -> u64 {
+    #[must_use]
+    pub fn hash_with_seeds<T: AsRef<[u8]>>(bytes: T, seed0: u64, seed1: u64) -> u64 {
         unsafe {
             ffi::farmhash64_with_seeds(bytes.as_ref().as_ptr() as *const i8,
                                        bytes.as_ref().len(),
@@ -165,6 +208,66 @@ impl FarmHash64 {
                                        seed1)
         }
     }
+
+    /// Hash functions for a byte array, selecting the SSE4.2 and AES-NI build
+    /// of `FarmHash` at runtime when the CPU supports it, and the portable build otherwise.
+    ///
+    /// The CPU features are detected once, and the decision is cached for later calls.
+    /// `FarmHash` explicitly permits platform dependent results, so the output may differ
+    /// between the two builds, and from `FarmHash64::hash`, which uses the build selected
+    /// at compile time; on other architectures it is the same as `FarmHash64::hash`.
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use]
+    pub fn hash_dispatched<T: AsRef<[u8]>>(bytes: T) -> u64 {
+        let bytes = bytes.as_ref();
+
+        unsafe { dispatched_farmhash64()(bytes.as_ptr() as *const i8, bytes.len()) }
+    }
+
+    /// Hash functions for a byte array, returning the hash as little-endian bytes.
+    #[inline]
+    #[must_use]
+    pub fn hash_array<T: AsRef<[u8]>>(bytes: T) -> [u8; 8] {
+        FarmHash64::hash(bytes).to_le_bytes()
+    }
+}
+
+#[cfg(feature = "std")]
+type Farmhash64Fn = unsafe extern "C" fn(*const i8, usize) -> u64;
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn dispatched_farmhash64() -> Farmhash64Fn {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const UNKNOWN: usize = 0;
+    const PORTABLE: usize = 1;
+    const SSE42: usize = 2;
+
+    static SELECTED: AtomicUsize = AtomicUsize::new(UNKNOWN);
+
+    let mut selected = SELECTED.load(Ordering::Relaxed);
+
+    if selected == UNKNOWN {
+        selected = if is_x86_feature_detected!("sse4.2") && is_x86_feature_detected!("aes") {
+            SSE42
+        } else {
+            PORTABLE
+        };
+
+        SELECTED.store(selected, Ordering::Relaxed);
+    }
+
+    if selected == SSE42 {
+        ffi::farmhash64_sse42
+    } else {
+        ffi::farmhash64_portable
+    }
+}
+
+#[cfg(all(feature = "std", not(any(target_arch = "x86", target_arch = "x86_64"))))]
+fn dispatched_farmhash64() -> Farmhash64Fn {
+    ffi::farmhash64
 }
 
 impl FastHash for FarmHash64 {
@@ -172,12 +275,12 @@ impl FastHash for FarmHash64 {
     type Seed = u64;
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u64 {
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u64 {
         unsafe { ffi::farmhash64(bytes.as_ref().as_ptr() as *const i8, bytes.as_ref().len()) }
     }
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::farmhash64_with_seed(bytes.as_ref().as_ptr() as *const i8,
                                       bytes.as_ref().len(),
@@ -188,39 +291,207 @@ impl FastHash for FarmHash64 {
 
 impl_hasher!(FarmHasher64, FarmHash64);
 
+/// `FarmHasher64` buffers the written bytes and hashes them once in `finish`,
+/// since `FarmHash` has no streaming state.
+#[cfg(feature = "alloc")]
+impl FarmHasher64 {
+    /// Hash a sequence of byte arrays as if they were concatenated.
+    ///
+    /// The slices are copied once into a single contiguous buffer, then hashed
+    /// with a single `FarmHash64::hash` call; a single slice is hashed in place.
+    #[must_use]
+    pub fn hash_slices(slices: &[&[u8]]) -> u64 {
+        match slices.len() {
+            0 => FarmHash64::hash(b""),
+            1 => FarmHash64::hash(slices[0]),
+            _ => FarmHash64::hash(slices.concat()),
+        }
+    }
+}
+
+/// A `BuildHasher` creating `FarmHasher64`s seeded from a stored seed.
+///
+/// The `Default` seed is zero, so maps are reproducible unless built with `random`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use fasthash::farm::FarmBuildHasher;
+///
+/// let mut map = HashMap::with_hasher(FarmBuildHasher::random());
+///
+/// map.insert("hello", 1);
+///
+/// assert_eq!(map.get("hello"), Some(&1));
+/// ```
+///
+/// A single value is hashed in one call with `BuildHasher::hash_one`.
+///
+/// ```
+/// use std::hash::{BuildHasher, Hash, Hasher};
+/// use fasthash::farm::FarmBuildHasher;
+///
+/// let farm_build = FarmBuildHasher::with_seed(123);
+/// let mut h = farm_build.build_hasher();
+///
+/// 42u64.hash(&mut h);
+///
+/// assert_eq!(farm_build.hash_one(&42u64), h.finish());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FarmBuildHasher {
+    seed: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl FarmBuildHasher {
+    /// Constructs a new `FarmBuildHasher` with the given seed.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        FarmBuildHasher { seed: seed }
+    }
+
+    /// Constructs a new `FarmBuildHasher` with a seed drawn once from the OS RNG.
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        let mut rng = OsRng::new().expect("failed to create an OS RNG");
+
+        FarmBuildHasher::with_seed(rng.gen())
+    }
+
+    /// Returns the seed of the built hashers.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BuildHasher for FarmBuildHasher {
+    type Hasher = FarmHasher64;
+
+    #[inline]
+    fn build_hasher(&self) -> FarmHasher64 {
+        FarmHasher64::with_seed(self.seed)
+    }
+}
+
 /// `FarmHash` 128-bit hash functions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct FarmHash128 {}
 
+impl FarmHash128 {
+    /// Hash functions for a byte array, seeded from 16 bytes.
+    ///
+    /// The seed is read as a little-endian `u128`, i.e. the first 8 bytes are
+    /// the low half and the last 8 bytes the high half, then passed to `hash_with_seed`.
+    #[inline]
+    #[must_use]
+    pub fn hash_with_seed_bytes<T: AsRef<[u8]>>(bytes: T, seed: &[u8; 16]) -> u128 {
+        FarmHash128::hash_with_seed(bytes, u128::from_le_bytes(*seed))
+    }
+
+    /// Hash functions for a byte array, writing the hash into `out` as little-endian bytes.
+    #[inline]
+    pub fn hash_into<T: AsRef<[u8]>>(bytes: T, out: &mut [u8; 16]) {
+        *out = FarmHash128::hash_array(bytes);
+    }
+
+    /// Hash functions for a byte array, returning the hash as little-endian bytes.
+    #[inline]
+    #[must_use]
+    pub fn hash_array<T: AsRef<[u8]>>(bytes: T) -> [u8; 16] {
+        FarmHash128::hash(bytes).to_le_bytes()
+    }
+}
+
 impl FastHash for FarmHash128 {
     type Value = u128;
     type Seed = u128;
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u128 {
-        unsafe {
-            mem::transmute(ffi::farmhash128(bytes.as_ref().as_ptr() as *const i8,
-                                            bytes.as_ref().len()))
-        }
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u128 {
+        from_uint128_c(unsafe {
+            ffi::farmhash128(bytes.as_ref().as_ptr() as *const i8, bytes.as_ref().len())
+        })
     }
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u128) -> u128 {
-        unsafe {
-            mem::transmute(ffi::farmhash128_with_seed(bytes.as_ref().as_ptr() as *const i8,
-                                                      bytes.as_ref().len(),
-                                                      mem::transmute(seed)))
-        }
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
+        from_uint128_c(unsafe {
+            ffi::farmhash128_with_seed(bytes.as_ref().as_ptr() as *const i8,
+                                       bytes.as_ref().len(),
+                                       to_uint128_c(seed))
+        })
     }
 }
 
 impl_hasher_ext!(FarmHasher128, FarmHash128);
 
+// `uint128_c_t` is passed by value to and from C, so a change of its size or alignment
+// would silently corrupt every 128-bit hash; fail the build instead.
+const _: () = assert!(mem::size_of::<ffi::uint128_c_t>() == mem::size_of::<u128>());
+const _: () = assert!(mem::align_of::<ffi::uint128_c_t>() == mem::align_of::<u64>());
+
+// `uint128_c_t` holds the low 64 bits in `a` and the high 64 bits in `b`,
+// convert by field rather than `transmute` so the value doesn't depend on the byte order.
+#[inline]
+fn from_uint128_c(v: ffi::uint128_c_t) -> u128 {
+    u128_from_parts(v.b, v.a)
+}
+
+#[inline]
+fn to_uint128_c(v: u128) -> ffi::uint128_c_t {
+    let (high, low) = u128_to_parts(v);
+
+    ffi::uint128_c_t {
+        a: low,
+        b: high,
+    }
+}
+
+macro_rules! impl_fingerprint {
+    ($hash:ident, $bits:expr, $value:ty, $fingerprint:ident) => (
+        /// `FarmHash`
+        #[doc = $bits]
+        /// fingerprint functions, which are a `StableHash`.
+        ///
+        /// The fingerprint is unseeded, the `()` seed is ignored.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $hash {}
+
+        impl FastHash for $hash {
+            type Value = $value;
+            type Seed = ();
+
+            #[inline]
+            fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, _seed: ()) -> $value {
+                $fingerprint(bytes)
+            }
+        }
+
+        impl StableHash for $hash {}
+    )
+}
+
+impl_fingerprint!(FarmFingerprint32, "32-bit", u32, fingerprint32);
+impl_hasher!(FarmFingerprintHasher32, FarmFingerprint32);
+
+impl_fingerprint!(FarmFingerprint64, "64-bit", u64, fingerprint64);
+impl_hasher!(FarmFingerprintHasher64, FarmFingerprint64);
+
+impl_fingerprint!(FarmFingerprint128, "128-bit", u128, fingerprint128);
+impl_hasher_ext!(FarmFingerprintHasher128, FarmFingerprint128);
+
 /// `FarmHash` 32-bit hash function for a byte array.
 ///
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+#[must_use]
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     FarmHash32::hash(v)
 }
 
@@ -230,34 +501,226 @@ pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+#[must_use]
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     FarmHash32::hash_with_seed(v, seed)
 }
 
 /// `FarmHash` 64-bit hash function for a byte array.
 ///
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+///
+/// Like the other hash functions, the result must be used:
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+///
+/// fasthash::farm::hash64(b"hello");
+/// ```
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+#[must_use]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     FarmHash64::hash(v)
 }
 
+/// `FarmHash` 64-bit hash function for a NUL-terminated C string.
+///
+/// Only the bytes before the first NUL byte are hashed, like `strlen` would see them,
+/// so the result matches C code hashing the same buffer as a C string.
+/// The whole slice is hashed if it doesn't contain a NUL byte; use `hash64` to hash
+/// all the bytes, including any interior NUL.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash64_cstr_truncated(bytes: &[u8]) -> u64 {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+
+    hash64(&bytes[..len])
+}
+
+/// `FarmHash` hash function for a byte array, truncated to `bits` bits.
+///
+/// Returns a value in `[0, 2^bits)` made of the high `bits` bits of `hash64`,
+/// which are better mixed than the low bits a mask would keep.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+///
+/// # Panics
+///
+/// Panics if `bits` is not in `1..=64`.
+#[inline]
+#[must_use]
+pub fn hash_to_bits(data: &[u8], bits: u32) -> u64 {
+    assert!(bits > 0 && bits <= 64, "bits must be in 1..=64");
+
+    hash64(data) >> (64 - bits)
+}
+
+/// `FarmHash` 64-bit hash function for a byte array, with the popcount of the hash.
+///
+/// Returns `(hash64_with_seed(data, seed), hash.count_ones())`, for the weighted
+/// Bloom and cuckoo filters deriving a weight from the number of set bits.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash64_with_popcount(data: &[u8], seed: u64) -> (u64, u32) {
+    let h = hash64_with_seed(data, seed);
+
+    (h, h.count_ones())
+}
+
+/// `FarmHash` 64-bit hash function for the logical content of a padded fixed-size record.
+///
+/// The trailing bytes equal to `pad_byte` are stripped before hashing, so the result
+/// matches `hash64` of the unpadded content. This is ambiguous if the content itself
+/// may end with `pad_byte`: `b"ab\0"` and `b"ab"` padded with `0` hash the same;
+/// hash the content length separately, e.g. with `hash64_framed`, if that matters.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash64_unpadded(record: &[u8], pad_byte: u8) -> u64 {
+    let len = record.iter().rposition(|&b| b != pad_byte).map_or(0, |i| i + 1);
+
+    hash64(&record[..len])
+}
+
+/// `FarmHash` 64-bit hash function for the content of a file.
+///
+/// The file is memory mapped with a sequential access advice, and the whole mapping
+/// is hashed with a single `hash64` call; if it can't be mapped, e.g. a pipe,
+/// it is read into a buffer instead. The file must not be modified while it is hashed.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "mmap")]
+pub fn hash64_file(path: &Path) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+
+    match unsafe { Mmap::map(&file) } {
+        Ok(mmap) => {
+            // the advice is only a hint, the mapping is hashed anyway
+            #[cfg(unix)]
+            let _ = mmap.advise(Advice::Sequential);
+
+            Ok(hash64(&mmap))
+        }
+        Err(_) => {
+            let mut buf = Vec::new();
+
+            file.read_to_end(&mut buf)?;
+
+            Ok(hash64(&buf))
+        }
+    }
+}
+
+/// A `FarmHash` 64-bit hash value, which can't be compared with other algorithms' values.
+///
+/// It derefs to `u64` for arithmetic.
+///
+/// ```compile_fail
+/// use fasthash::{farm, spooky};
+///
+/// assert!(farm::hash64_typed(b"hello") != spooky::hash64_typed(b"hello"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FarmHash64Value(pub u64);
+
+impl Deref for FarmHash64Value {
+    type Target = u64;
+
+    #[inline]
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+/// `FarmHash` 64-bit hash function for a byte array, returning a typed value.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash64_typed<T: AsRef<[u8]>>(v: T) -> FarmHash64Value {
+    FarmHash64Value(hash64(v))
+}
+
 /// `FarmHash` 64-bit hash function for a byte array.
 /// For convenience, a 64-bit seed is also hashed into the result.
 ///
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+#[must_use]
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     FarmHash64::hash_with_seed(v, seed)
 }
 
+/// `FarmHash` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+#[must_use]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
+/// `FarmHash` 64-bit hash function for a string, as hashed through the `Hash` trait.
+///
+/// The `Hash` implementation of `str` writes the UTF-8 bytes followed by a `0xff` byte,
+/// so that `("ab", "c")` and `("a", "bc")` hash differently; the result is therefore
+/// `hash64` of the bytes and the terminator, e.g. `hash64(b"hello world\xff")`,
+/// and matches the hash of the string as the key of a map built with `FarmHasher`.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_hashlike(s: &str) -> u64 {
+    let mut h = FarmHasher64::new();
+
+    s.hash(&mut h);
+    h.finish()
+}
+
+/// `FarmHash` 64-bit hash function for `len` bytes at `ptr`.
+///
+/// The same as `hash64`, for callers holding a raw pointer, e.g. to foreign-owned memory.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes.
+#[inline]
+#[must_use]
+pub unsafe fn hash64_raw(ptr: *const u8, len: usize) -> u64 {
+    ffi::farmhash64(ptr as *const i8, len)
+}
+
+/// `FarmHash` 64-bit hash function for a fixed-size key, e.g. a `[u8; 8]` row id.
+/// For convenience, a 64-bit seed is also hashed into the result.
+///
+/// The length is known at compile time, so each `N` gets its own monomorphized
+/// wrapper; the result is identical to `hash64_with_seed` over the same bytes.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+///
+#[inline(always)]
+#[must_use]
+pub fn hash64_fixed<const N: usize>(v: &[u8; N], seed: u64) -> u64 {
+    unsafe { ffi::farmhash64_with_seed(v.as_ptr() as *const i8, N, seed) }
+}
+
 /// `FarmHash` 64-bit hash function for a byte array.
 /// For convenience, two seeds are also hashed into the result.
 ///
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
-pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: &T, seed0: u64, seed1: u64) -> u64 {
+#[must_use]
+pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
     FarmHash64::hash_with_seeds(v, seed0, seed1)
 }
 
@@ -266,128 +729,2563 @@ pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: &T, seed0: u64, seed1: u64) -> u64 {
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+#[must_use]
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     FarmHash128::hash(v)
 }
 
+/// The 32, 64 and 128-bit `FarmHash` values of a byte array, see `hash_all`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HashTriple {
+    /// The high 32 bits of `h128`.
+    pub h32: u32,
+    /// The low 64 bits of `h128`.
+    pub h64: u64,
+    /// The `hash128` of the byte array.
+    pub h128: u128,
+}
+
+/// `FarmHash` 32, 64 and 128-bit hash values of a byte array, from a single pass over it.
+///
+/// Only `hash128` is computed: `h64` is its low 64 bits, and `h32` its high 32 bits,
+/// so `h32` and `h64` don't share any bit. They differ from `hash32` and `hash64`.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash_all(data: &[u8]) -> HashTriple {
+    let h = hash128(data);
+
+    HashTriple {
+        h32: (h >> 96) as u32,
+        h64: h as u64,
+        h128: h,
+    }
+}
+
 /// `FarmHash` 128-bit hash function for a byte array.
 /// For convenience, a 128-bit seed is also hashed into the result.
 ///
 /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
 ///
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u128) -> u128 {
+#[must_use]
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
     FarmHash128::hash_with_seed(v, seed)
 }
 
 /// `FarmHash` 32-bit fingerprint function for a byte array.
 #[inline]
-pub fn fingerprint32<T: AsRef<[u8]>>(v: &T) -> u32 {
+#[must_use]
+pub fn fingerprint32<T: AsRef<[u8]>>(v: T) -> u32 {
     unsafe { ffi::farmhash_fingerprint32(v.as_ref().as_ptr() as *const i8, v.as_ref().len()) }
 }
 
 /// `FarmHash` 64-bit fingerprint function for a byte array.
 #[inline]
-pub fn fingerprint64<T: AsRef<[u8]>>(v: &T) -> u64 {
+#[must_use]
+pub fn fingerprint64<T: AsRef<[u8]>>(v: T) -> u64 {
     unsafe { ffi::farmhash_fingerprint64(v.as_ref().as_ptr() as *const i8, v.as_ref().len()) }
 }
 
-/// `FarmHash` 128-bit fingerprint function for a byte array.
+/// `FarmHash` 64-bit hash function for a byte array, with a forever-fixed portable output.
+///
+/// Unlike `hash64`, which may change from time to time and differs with the variant
+/// selected for the build, the result is the same on every platform and in every version,
+/// so it may be persisted. `FarmHash` has no `farmhashcc::Hash64`, the `cc` variants
+/// only provide 32 and 128-bit functions; the fixed 64-bit variant is `farmhashna::Hash64`,
+/// which `fingerprint64` is defined as, so this is `fingerprint64`.
 #[inline]
-pub fn fingerprint128<T: AsRef<[u8]>>(v: &T) -> u128 {
-    unsafe {
-        mem::transmute(ffi::farmhash_fingerprint128(v.as_ref().as_ptr() as *const i8,
-                                                    v.as_ref().len()))
-    }
-}
-
-impl Fingerprint<u64> for u64 {
-    #[inline]
-    fn fingerprint(&self) -> u64 {
-        unsafe { ffi::farmhash_fingerprint_uint64(*self) }
-    }
+#[must_use]
+pub fn hash64_cc(bytes: &[u8]) -> u64 {
+    fingerprint64(bytes)
 }
 
-impl Fingerprint<u64> for u128 {
-    #[inline]
-    fn fingerprint(&self) -> u64 {
-        unsafe { ffi::farmhash_fingerprint_uint128(mem::transmute(*self)) }
-    }
+/// `FarmHash` 128-bit fingerprint function for a byte array.
+#[inline]
+#[must_use]
+pub fn fingerprint128<T: AsRef<[u8]>>(v: T) -> u128 {
+    from_uint128_c(unsafe {
+        ffi::farmhash_fingerprint128(v.as_ref().as_ptr() as *const i8, v.as_ref().len())
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use std::hash::Hasher;
-
-    use extprim::u128::u128;
+macro_rules! impl_fingerprint_value {
+    ($name:ident, $value:ty, $bits:expr, $fingerprint:ident, $digits:expr) => (
+        /// A `FarmHash`
+        #[doc = $bits]
+        /// fingerprint, which is forever-fixed and may be persisted.
+        ///
+        /// It formats and parses as fixed width lower case hex. With the `serde` feature it
+        /// serializes as the integer, or as the hex string with `#[serde(with = "farm::hex")]`.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name(pub $value);
 
-    use hasher::{Fingerprint, FastHash, FastHasher, HasherExt};
-    use super::*;
+        impl $name {
+            /// Fingerprints the byte array.
+            #[inline]
+            pub fn of<T: AsRef<[u8]>>(v: T) -> Self {
+                $name($fingerprint(v))
+            }
+        }
 
-    #[test]
-    fn test_farmhash32() {
-        let h1 = FarmHash32::hash(b"hello");
-        let h2 = FarmHash32::hash_with_seed(b"hello", 123);
-        let h3 = FarmHash32::hash(b"helloworld");
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, concat!("{:0", $digits, "x}"), self.0)
+            }
+        }
 
-        assert!(h1 != 0);
-        assert!(h2 != 0);
-        assert!(h3 != 0);
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::LowerHex::fmt(self, f)
+            }
+        }
 
-        let mut h = FarmHasher32::new();
+        impl str::FromStr for $name {
+            type Err = ParseIntError;
 
-        h.write(b"hello");
-        assert_eq!(h.finish(), h1 as u64);
+            /// Parses the hex digits of a fingerprint.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                <$value>::from_str_radix(s, 16).map($name)
+            }
+        }
+    )
+}
 
-        h.write(b"world");
-        assert_eq!(h.finish(), h3 as u64);
-    }
+impl_fingerprint_value!(Fingerprint64, u64, "64-bit", fingerprint64, 16);
+impl_fingerprint_value!(Fingerprint128, u128, "128-bit", fingerprint128, 32);
 
-    #[test]
-    fn test_farmhash64() {
-        assert_eq!(FarmHash64::hash(b"hello"), 14403600180753024522);
-        assert_eq!(FarmHash64::hash_with_seed(b"hello", 123),
-                   6856739100025169098);
-        assert_eq!(FarmHash64::hash_with_seeds(b"hello", 123, 456),
-                   15077713332534145879);
-        assert_eq!(FarmHash64::hash(b"helloworld"), 1077737941828767314);
+/// Serializes the `Fingerprint64` and `Fingerprint128` fingerprints as hex strings.
+///
+/// # Example
+///
+/// ```
+/// # extern crate serde;
+/// # extern crate fasthash;
+/// use serde::{Deserialize, Serialize};
+///
+/// use fasthash::farm::{self, Fingerprint64};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Blob {
+///     #[serde(with = "farm::hex")]
+///     fingerprint: Fingerprint64,
+/// }
+/// # fn main() {}
+/// ```
+#[cfg(feature = "serde")]
+pub mod hex {
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
 
-        let mut h = FarmHasher64::new();
+    use serde::{de, Deserializer, Serializer};
 
-        h.write(b"hello");
-        assert_eq!(h.finish(), 14403600180753024522);
+    /// Serializes the value as its `LowerHex` string.
+    pub fn serialize<T, S>(v: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: fmt::LowerHex,
+              S: Serializer
+    {
+        serializer.collect_str(&format_args!("{:x}", v))
+    }
 
-        h.write(b"world");
-        assert_eq!(h.finish(), 1077737941828767314);
+    /// Deserializes the value from a string parsed with `FromStr`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: FromStr,
+              T::Err: fmt::Display,
+              D: Deserializer<'de>
+    {
+        deserializer.deserialize_str(HexVisitor(PhantomData))
     }
 
-    #[test]
-    fn test_farmhash128() {
-        assert_eq!(FarmHash128::hash(b"hello"),
-                   u128::from_parts(14545675544334878584, 15888401098353921598));
-        assert_eq!(FarmHash128::hash_with_seed(b"hello", u128::new(123)),
-                   u128::from_parts(15212901187400903054, 13320390559359511083));
-        assert_eq!(FarmHash128::hash(b"helloworld"),
-                   u128::from_parts(16066658700231169910, 1119455499735156801));
+    struct HexVisitor<T>(PhantomData<T>);
 
-        let mut h = FarmHasher128::new();
+    impl<'de, T> de::Visitor<'de> for HexVisitor<T>
+        where T: FromStr,
+              T::Err: fmt::Display
+    {
+        type Value = T;
 
-        h.write(b"hello");
-        assert_eq!(h.finish_ext(),
-                   u128::from_parts(14545675544334878584, 15888401098353921598));
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a hex fingerprint")
+        }
 
-        h.write(b"world");
-        assert_eq!(h.finish_ext(),
-                   u128::from_parts(16066658700231169910, 1119455499735156801));
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+}
+
+/// `FarmHash` 64-bit fingerprint function for a byte array, evaluable at compile time.
+///
+/// A pure Rust port of the `Fingerprint64` algorithm, which returns the same values
+/// as `fingerprint64`, so it can build `const` keys and `static` hash tables.
+/// There is no `const` version of `hash64`, since `FarmHash` picks a platform dependent
+/// variant for it at build time, while the fingerprint is forever-fixed.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::farm;
+///
+/// const KEY: u64 = farm::fingerprint64_const(b"hello word");
+///
+/// assert_eq!(KEY, farm::fingerprint64(b"hello word"));
+/// ```
+#[cfg(feature = "const-hash")]
+#[inline]
+#[must_use]
+pub const fn fingerprint64_const(v: &[u8]) -> u64 {
+    na::hash64(v)
+}
+
+#[cfg(feature = "const-hash")]
+mod na {
+    const K0: u64 = 0xc3a5c85c97cb3127;
+    const K1: u64 = 0xb492b66fbe98f273;
+    const K2: u64 = 0x9ae16a3b2f90404f;
+
+    const fn fetch64(s: &[u8], i: usize) -> u64 {
+        u64::from_le_bytes([s[i], s[i + 1], s[i + 2], s[i + 3], s[i + 4], s[i + 5], s[i + 6],
+                            s[i + 7]])
+    }
+
+    const fn fetch32(s: &[u8], i: usize) -> u64 {
+        u32::from_le_bytes([s[i], s[i + 1], s[i + 2], s[i + 3]]) as u64
+    }
+
+    const fn shift_mix(v: u64) -> u64 {
+        v ^ (v >> 47)
+    }
+
+    const fn hash_len16(u: u64, v: u64, mul: u64) -> u64 {
+        let a = shift_mix((u ^ v).wrapping_mul(mul));
+        let b = shift_mix((v ^ a).wrapping_mul(mul));
+
+        b.wrapping_mul(mul)
+    }
+
+    const fn hash_len0to16(s: &[u8]) -> u64 {
+        let len = s.len();
+        let mul = K2.wrapping_add(len as u64 * 2);
+
+        if len >= 8 {
+            let a = fetch64(s, 0).wrapping_add(K2);
+            let b = fetch64(s, len - 8);
+            let c = b.rotate_right(37).wrapping_mul(mul).wrapping_add(a);
+            let d = a.rotate_right(25).wrapping_add(b).wrapping_mul(mul);
+
+            hash_len16(c, d, mul)
+        } else if len >= 4 {
+            let a = fetch32(s, 0);
+
+            hash_len16((len as u64).wrapping_add(a << 3), fetch32(s, len - 4), mul)
+        } else if len > 0 {
+            let a = s[0] as u32;
+            let b = s[len >> 1] as u32;
+            let c = s[len - 1] as u32;
+            let y = a.wrapping_add(b << 8) as u64;
+            let z = (len as u32).wrapping_add(c << 2) as u64;
+
+            shift_mix(y.wrapping_mul(K2) ^ z.wrapping_mul(K0)).wrapping_mul(K2)
+        } else {
+            K2
+        }
+    }
+
+    // the two halves shared by `HashLen17to32` and `HashLen33to64`
+    const fn mix_len16(a: u64, b: u64, c: u64, d: u64) -> (u64, u64) {
+        let u = a.wrapping_add(b).rotate_right(43).wrapping_add(c.rotate_right(30)).wrapping_add(d);
+        let v = a.wrapping_add(b.wrapping_add(K2).rotate_right(18)).wrapping_add(c);
+
+        (u, v)
+    }
+
+    const fn hash_len17to32(s: &[u8]) -> u64 {
+        let len = s.len();
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch64(s, 0).wrapping_mul(K1);
+        let b = fetch64(s, 8);
+        let c = fetch64(s, len - 8).wrapping_mul(mul);
+        let d = fetch64(s, len - 16).wrapping_mul(K2);
+        let (u, v) = mix_len16(a, b, c, d);
+
+        hash_len16(u, v, mul)
+    }
+
+    const fn hash_len33to64(s: &[u8]) -> u64 {
+        let len = s.len();
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch64(s, 0).wrapping_mul(K2);
+        let b = fetch64(s, 8);
+        let c = fetch64(s, len - 8).wrapping_mul(mul);
+        let d = fetch64(s, len - 16).wrapping_mul(K2);
+        let (y, v) = mix_len16(a, b, c, d);
+        let z = hash_len16(y, v, mul);
+        let e = fetch64(s, 16).wrapping_mul(mul);
+        let f = fetch64(s, 24);
+        let g = y.wrapping_add(fetch64(s, len - 32)).wrapping_mul(mul);
+        let h = z.wrapping_add(fetch64(s, len - 24)).wrapping_mul(mul);
+        let u = e.wrapping_add(f).rotate_right(43).wrapping_add(g.rotate_right(30)).wrapping_add(h);
+        let v = e.wrapping_add(f.wrapping_add(a).rotate_right(18)).wrapping_add(g);
+
+        hash_len16(u, v, mul)
+    }
+
+    const fn weak_hash_len32_with_seeds(s: &[u8], i: usize, a: u64, b: u64) -> (u64, u64) {
+        let w = fetch64(s, i);
+        let x = fetch64(s, i + 8);
+        let y = fetch64(s, i + 16);
+        let z = fetch64(s, i + 24);
+
+        let a = a.wrapping_add(w);
+        let b = b.wrapping_add(a).wrapping_add(z).rotate_right(21);
+        let c = a;
+        let a = a.wrapping_add(x).wrapping_add(y);
+        let b = b.wrapping_add(a.rotate_right(44));
+
+        (a.wrapping_add(z), b.wrapping_add(c))
+    }
+
+    /// `farmhashna::Hash64`, which `Fingerprint64` is defined as.
+    pub const fn hash64(s: &[u8]) -> u64 {
+        const SEED: u64 = 81;
+
+        let len = s.len();
+
+        if len <= 16 {
+            return hash_len0to16(s);
+        } else if len <= 32 {
+            return hash_len17to32(s);
+        } else if len <= 64 {
+            return hash_len33to64(s);
+        }
+
+        let mut x = SEED.wrapping_mul(K2).wrapping_add(fetch64(s, 0));
+        let mut y = SEED.wrapping_mul(K1).wrapping_add(113);
+        let mut z = shift_mix(y.wrapping_mul(K2).wrapping_add(113)).wrapping_mul(K2);
+        let mut v = (0, 0);
+        let mut w = (0, 0);
+
+        let end = ((len - 1) / 64) * 64;
+        let last64 = end + ((len - 1) & 63) - 63;
+        let mut p = 0;
+
+        // `x` and `z` are swapped at the end of each round
+        while p != end {
+            let t = x.wrapping_add(y).wrapping_add(v.0).wrapping_add(fetch64(s, p + 8));
+
+            x = t.rotate_right(37).wrapping_mul(K1);
+            y = y.wrapping_add(v.1).wrapping_add(fetch64(s, p + 48));
+            y = y.rotate_right(42).wrapping_mul(K1);
+            x ^= w.1;
+            y = y.wrapping_add(v.0).wrapping_add(fetch64(s, p + 40));
+            z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
+            v = weak_hash_len32_with_seeds(s, p, v.1.wrapping_mul(K1), x.wrapping_add(w.0));
+            w = weak_hash_len32_with_seeds(s,
+                                           p + 32,
+                                           z.wrapping_add(w.1),
+                                           y.wrapping_add(fetch64(s, p + 16)));
+
+            let t = z;
+
+            z = x;
+            x = t;
+            p += 64;
+        }
+
+        let mul = K1.wrapping_add((z & 0xff) << 1);
+        let p = last64;
+
+        w.0 = w.0.wrapping_add(((len - 1) & 63) as u64);
+        v.0 = v.0.wrapping_add(w.0);
+        w.0 = w.0.wrapping_add(v.0);
+
+        let t = x.wrapping_add(y).wrapping_add(v.0).wrapping_add(fetch64(s, p + 8));
+
+        x = t.rotate_right(37).wrapping_mul(mul);
+        y = y.wrapping_add(v.1).wrapping_add(fetch64(s, p + 48));
+        y = y.rotate_right(42).wrapping_mul(mul);
+        x ^= w.1.wrapping_mul(9);
+        y = y.wrapping_add(v.0.wrapping_mul(9)).wrapping_add(fetch64(s, p + 40));
+        z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(mul);
+        v = weak_hash_len32_with_seeds(s, p, v.1.wrapping_mul(mul), x.wrapping_add(w.0));
+        w = weak_hash_len32_with_seeds(s,
+                                       p + 32,
+                                       z.wrapping_add(w.1),
+                                       y.wrapping_add(fetch64(s, p + 16)));
+
+        // the final swap of `x` and `z` is folded into the result
+        let u = hash_len16(v.0, w.0, mul)
+            .wrapping_add(shift_mix(y).wrapping_mul(K0))
+            .wrapping_add(x);
+        let v = hash_len16(v.1, w.1, mul).wrapping_add(z);
+
+        hash_len16(u, v, mul)
+    }
+}
+
+/// `FarmHash` 64-bit hash of any `T: Hash` value, not only of a byte array.
+///
+/// The value is fed through its `Hash` implementation into a `FarmHasher64`,
+/// the stateful hasher, which buffers all the writes and hashes them once in `finish`,
+/// so a given value always produces the same result.
+/// It doesn't use the raw fingerprint functions, and differs from `fingerprint64(bytes)`,
+/// e.g. a `str` also writes a trailing `0xff` byte.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn fingerprint_value<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut h = FarmHasher64::new();
+
+    value.hash(&mut h);
+
+    h.finish()
+}
+
+/// `FarmHash` 64-bit hash function for a `f64` numeric key.
+///
+/// The value is canonicalized before hashing its little-endian bytes,
+/// `-0.0` hashes as `0.0` and all the NaN bit patterns hash as `f64::NAN`,
+/// so logically equal floats produce the same result.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash64_f64(v: f64) -> u64 {
+    let bits = if v.is_nan() {
+        f64::NAN.to_bits()
+    } else if v == 0.0 {
+        0
+    } else {
+        v.to_bits()
+    };
+
+    hash64(bits.to_le_bytes())
+}
+
+/// `FarmHash` 64-bit hash function for a `f32` numeric key.
+///
+/// The value is canonicalized like `hash64_f64` before hashing its little-endian bytes.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+#[must_use]
+pub fn hash64_f32(v: f32) -> u64 {
+    let bits = if v.is_nan() {
+        f32::NAN.to_bits()
+    } else if v == 0.0 {
+        0
+    } else {
+        v.to_bits()
+    };
+
+    hash64(bits.to_le_bytes())
+}
+
+/// `FarmHash` 64-bit hash function for the `Debug` representation of a value.
+///
+/// The formatted output is written straight into a `FarmHasher64`, without building
+/// an intermediate `String`. The result is only as stable as the type's `Debug` output,
+/// so it suits quick cache keys rather than persisted ones.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_debug<T: fmt::Debug + ?Sized>(v: &T) -> u64 {
+    struct DebugWriter<'a>(&'a mut FarmHasher64);
+
+    impl<'a> fmt::Write for DebugWriter<'a> {
+        #[inline]
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.write(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    let mut h = FarmHasher64::new();
+
+    fmt::write(&mut DebugWriter(&mut h), format_args!("{:?}", v))
+        .expect("a Debug implementation returned an error");
+
+    h.finish()
+}
+
+/// `FarmHash` 128-bit hash function for a batch of byte arrays, in a struct-of-arrays layout.
+///
+/// The high and low words of the hash of `inputs[i]` are written to `highs[i]` and `lows[i]`,
+/// so the results can be filtered by vectorized code downstream.
+///
+/// # Panics
+///
+/// Panics if `highs` or `lows` does not have the same length as `inputs`.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+pub fn hash128_batch_soa(inputs: &[&[u8]], highs: &mut [u64], lows: &mut [u64]) {
+    assert_eq!(inputs.len(), highs.len(), "highs length must match inputs");
+    assert_eq!(inputs.len(), lows.len(), "lows length must match inputs");
+
+    for ((input, high), low) in inputs.iter().zip(highs.iter_mut()).zip(lows.iter_mut()) {
+        let (h, l) = u128_to_parts(hash128(input));
+
+        *high = h;
+        *low = l;
+    }
+}
+
+/// `FarmHash` 64-bit hash function for a byte array that must be valid UTF-8.
+///
+/// The input is validated first and then hashed in one shot, as FarmHash can't hash
+/// a byte stream incrementally. On success the result equals `hash64(bytes)`;
+/// on failure the `Utf8Error` is the one `std::str::from_utf8` returns.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+pub fn hash64_validate_utf8(bytes: &[u8]) -> Result<u64, str::Utf8Error> {
+    str::from_utf8(bytes)?;
+
+    Ok(hash64(bytes))
+}
+
+/// Returns a deterministic per-key jitter in `[-1, 1)`, derived from the `FarmHash` 64-bit hash.
+///
+/// Spreading cache expirations with `ttl * (1.0 + 0.1 * jitter_fraction(key, seed))`
+/// keeps keys written at the same time from all expiring together,
+/// while a given key always gets the same TTL.
+/// The top 53 bits of the hash are used, so the values are uniformly distributed.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[inline]
+pub fn jitter_fraction(key: &[u8], seed: u64) -> f64 {
+    let h = hash64_with_seed(key, seed);
+
+    (h >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+/// `FarmHash` 64-bit hash function for a byte array, after a secret permutation of its bytes.
+///
+/// The bytes are shuffled with a Fisher-Yates shuffle keyed by `combine64(secret, index)`,
+/// then hashed with `hash64`, so the mapping from content to hash depends on the secret.
+/// This is a cheap obfuscation, not cryptography: the permutation doesn't change the
+/// multiset of bytes, and the secret may be recovered from enough known inputs and hashes.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_permuted(data: &[u8], secret: u64) -> u64 {
+    let mut buf = data.to_vec();
+
+    for i in (1..buf.len()).rev() {
+        let j = combine64(secret, i as u64) % (i as u64 + 1);
+
+        buf.swap(i, j as usize);
+    }
+
+    hash64(&buf)
+}
+
+/// Returns `true` if the linked `FarmHash` was built with its fixed, portable variants.
+///
+/// The answer comes from the C library itself, which reports the instructions
+/// it was compiled with. With the `sse42` feature, or any other flags enabling
+/// SSE4.1, SSE4.2 or AES-NI, `FarmHash` selects the vectorized variants of the `hash*`
+/// functions, whose results differ from the portable ones.
+/// Otherwise, the `hash*` functions match other portable builds for the same
+/// architecture. The `fingerprint*` functions are reproducible in both cases.
+#[inline]
+pub fn is_reproducible_build() -> bool {
+    unsafe { ffi::farmhash_is_portable() != 0 }
+}
+
+/// The revision of the vendored `farmhash-c` mixed into `version_tag`,
+/// bumped with every update of the library.
+const FARMHASH_C_REVISION: u64 = 1;
+
+/// Returns a tag identifying the `FarmHash` variant behind the `hash*` functions of this build.
+///
+/// The tag combines the revision of the vendored library, the variant the C library
+/// reports it was built with, `"sse42"` or `"portable"` (see `is_reproducible_build`),
+/// the target endianness, and the `hash*` values of a fixed probe, at lengths on both
+/// sides of the 16, 32, 64 and 512-byte branches of the `FarmHash` variants,
+/// so any change of their output changes the tag too.
+/// Store it next to long-lived `hash*` values, and reject the values on read
+/// when the tag differs, since they were produced by another variant or version.
+///
+/// The tag is the same for every call within a build, and never zero.
+pub fn version_tag() -> u64 {
+    let variant = if is_reproducible_build() { "portable" } else { "sse42" };
+    let endian = if cfg!(target_endian = "big") { "big" } else { "little" };
+
+    let mut probe = [0_u8; 1024];
+
+    for (i, b) in probe.iter_mut().enumerate() {
+        *b = (i * 131 + 7) as u8;
+    }
+
+    let tag = combine(&[fingerprint64(b"farmhash-c"),
+                        FARMHASH_C_REVISION,
+                        fingerprint64(variant),
+                        fingerprint64(endian)]);
+    let lengths = [0, 4, 8, 16, 17, 32, 33, 64, 65, 128, 300, 511, 512, 1024];
+    let tag = lengths.iter().fold(tag, |tag, &len| {
+        let v = &probe[..len];
+
+        combine(&[tag,
+                  hash32(v) as u64,
+                  hash64(v),
+                  hash64_with_seed(v, len as u64),
+                  hash_128_to_64(hash128(v))])
+    });
+
+    match tag {
+        0 => 1,
+        tag => tag,
+    }
+}
+
+/// The `(len, fingerprint32, fingerprint64, fingerprint128)` checked by `run_selftest`
+/// for the `len` bytes at offset `len * len` of `selftest_data`.
+///
+/// Taken from the `expected` arrays of `farmhashmkTest`, `farmhashnaTest` and `farmhashccTest`
+/// in the vendored `farmhash.cc`, which back `Fingerprint32`, `Fingerprint64` and `Fingerprint128`.
+#[cfg(all(feature = "alloc", debug_assertions))]
+const SELFTEST_VECTORS: [(usize, u32, u64, u128); 22] = [
+    (0, 3696677242, 11160318154034397263, (4374473821787594281 << 64) | 4463240938071824939),
+    (1, 2576519988, 6057711933287653014, (3176040433271522038 << 64) | 14109148841252173075),
+    (4, 653440099, 1287846036432493960, (7377518938879370555 << 64) | 5551218906389820565),
+    (5, 2957164615, 9448345910734356659, (15181945645271265282 << 64) | 990930410241563755),
+    (12, 2303349487, 16426460226004650093, (3650706225053739314 << 64) | 15650661220326174316),
+    (13, 595525107, 9592666280018697376, (16271662594447518122 << 64) | 8621240629310118382),
+    (15, 1592885012, 4919969303722224776, (323758208081772655 << 64) | 4299557419425032067),
+    (16, 2101378090, 282273162512842838, (10724492646739372455 << 64) | 12395478579055453811),
+    (17, 2118965695, 7691019942193281883, (1625751291496686847 << 64) | 11833020626001362050),
+    (24, 2330406762, 3936312831952133527, (16851541064023381706 << 64) | 7989939995056765678),
+    (25, 967660719, 992916931252359703, (14820663604188483911 << 64) | 952089062709307736),
+    (31, 1176094482, 6178288770513559229, (11533817178193642514 << 64) | 12889963372962943255),
+    (32, 2178982315, 541269899254986215, (15584803380685138962 << 64) | 11136349780346597486),
+    (33, 2660755208, 711203541633906104, (543406151410519426 << 64) | 8678566616348961885),
+    (63, 2636437533, 12172596441206182967, (9231192184761314293 << 64) | 10451554072592476095),
+    (64, 140995728, 16066393710399699995, (17047562376693298501 << 64) | 15119797713623969650),
+    (65, 1805716888, 4381627749513796415, (1032337923377228961 << 64) | 15269571930771738532),
+    (127, 4113026751, 11027467577531522736, (13499279810857945195 << 64) | 9635703462090085639),
+    (128, 3828347401, 17629800649466220701, (5381043520269593448 << 64) | 12521464769262087774),
+    (129, 830987972, 15345954539186508726, (9706567404540929073 << 64) | 927795198977630964),
+    (200, 2488161225, 4680135810112858625, (18155516594100244155 << 64) | 9513373098361054016),
+    (298, 2300310870, 2319855510833732255, (15304679027971986880 << 64) | 10844271039029394730),
+];
+
+/// The pseudorandom bytes hashed by the self test of the vendored `farmhash.cc`, see its `Setup`.
+#[cfg(all(feature = "alloc", debug_assertions))]
+fn selftest_data(len: usize) -> Vec<u8> {
+    const K0: u64 = 0xc3a5c85c97cb3127;
+
+    let mut a = 9_u64;
+    let mut b = 777_u64;
+
+    (0..len)
+        .map(|i| {
+            a = a.wrapping_add(b);
+            b = b.wrapping_add(a);
+            a = (a ^ (a >> 41)).wrapping_mul(K0);
+            b = (b ^ (b >> 41)).wrapping_mul(K0).wrapping_add(i as u64);
+            (b >> 37) as u8
+        })
+        .collect()
+}
+
+/// The integers checked by `run_selftest`, with their fingerprints.
+#[cfg(all(feature = "alloc", debug_assertions))]
+const SELFTEST_U64: [(u64, u64); 4] = [(0, 0),
+                                       (1, 10105606910506535461),
+                                       (123, 4781265650859502840),
+                                       (u64::MAX, 3896215773760724271)];
+#[cfg(all(feature = "alloc", debug_assertions))]
+const SELFTEST_U128: [(u128, u64); 4] = [(0, 0),
+                                         (123, 4011577241381678309),
+                                         ((123 << 64) | 456, 6719493898316549440),
+                                         (u128::MAX, 3896215773760724271)];
+
+/// Checks the linked `FarmHash` against known values, like the `FARMHASHSELFTEST` of the C library.
+///
+/// Only the forever-fixed `fingerprint*` functions are checked: `fingerprint32`,
+/// `fingerprint64`, `fingerprint128` and the integer fingerprints must produce
+/// the same values on every platform, so a mismatch means a broken build of the C library.
+/// The byte arrays are a slice of the vectors of the vendored self test,
+/// with lengths on both sides of 16, 32, 64 and 128 bytes.
+/// `hash32`, `hash32_with_seed` and the other `hash*` functions are not checked,
+/// since their results depend on the variant selected for the build (see `is_reproducible_build`).
+///
+/// Like `FARMHASHSELFTEST`, this is a debugging aid: it is only compiled with `debug_assertions`,
+/// and is meant to be called from a test or a debug build with
+/// `farm::run_selftest().expect("FarmHash self-test")`.
+///
+/// Returns an error describing the first mismatch.
+#[cfg(all(feature = "alloc", debug_assertions))]
+pub fn run_selftest() -> Result<(), String> {
+    fn check<T: PartialEq + fmt::Display>(name: &str,
+                                          input: &dyn fmt::Display,
+                                          got: T,
+                                          expected: T)
+                                          -> Result<(), String> {
+        if got == expected {
+            Ok(())
+        } else {
+            Err(format!("{}({}) = {}, expected {}", name, input, got, expected))
+        }
+    }
+
+    let (max_len, ..) = SELFTEST_VECTORS[SELFTEST_VECTORS.len() - 1];
+    let data = selftest_data(max_len * max_len + max_len);
+
+    for &(len, h32, h64, h128) in &SELFTEST_VECTORS {
+        let offset = len * len;
+        let bytes = &data[offset..offset + len];
+        let input = format!("data[{}..{}]", offset, offset + len);
+
+        check("farm::fingerprint32", &input, fingerprint32(bytes), h32)?;
+        check("farm::fingerprint64", &input, fingerprint64(bytes), h64)?;
+        check("farm::fingerprint128", &input, fingerprint128(bytes), h128)?;
+    }
+
+    for &(v, h) in &SELFTEST_U64 {
+        check("u64::fingerprint", &v, v.fingerprint(), h)?;
+    }
+
+    for &(v, h) in &SELFTEST_U128 {
+        check("u128::fingerprint", &v, v.fingerprint(), h)?;
+    }
+
+    Ok(())
+}
+
+/// `FarmHash` 64-bit salted fingerprint function for a byte array.
+///
+/// The salt is framed by its length, written as a little-endian `u64`,
+/// and hashed before the data through the stable fingerprint path,
+/// so the result is reproducible across platforms for the same salt.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn fingerprint64_salted<S: AsRef<[u8]>, T: AsRef<[u8]>>(salt: S, v: T) -> u64 {
+    let salt = salt.as_ref();
+    let v = v.as_ref();
+
+    let mut buf = Vec::with_capacity(8 + salt.len() + v.len());
+
+    buf.extend_from_slice(&(salt.len() as u64).to_le_bytes());
+    buf.extend_from_slice(salt);
+    buf.extend_from_slice(v);
+
+    fingerprint64(&buf)
+}
+
+/// `FarmHash` 128-bit fingerprint of an object, framed like Git object ids.
+///
+/// The `"<type> <size>\0"` header, e.g. `"blob 12\0"`, is prepended to the content
+/// before hashing with `fingerprint128`, instead of SHA-1.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash128_git_style(obj_type: &str, content: &[u8]) -> u128 {
+    let mut buf = format!("{} {}\0", obj_type, content.len()).into_bytes();
+
+    buf.extend_from_slice(content);
+
+    fingerprint128(&buf)
+}
+
+/// `FarmHash` 64-bit hash function for a sequence of byte arrays.
+///
+/// Each part is framed by its length, written as a little-endian `u64` regardless of
+/// the host endianness, so `["ab", "c"]` and `["a", "bc"]` produce different results.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_framed<T: AsRef<[u8]>>(parts: &[T]) -> u64 {
+    hash64(frame_parts(parts, u64::to_le_bytes))
+}
+
+/// `FarmHash` 64-bit hash function for a sequence of byte arrays.
+///
+/// Same as `hash64_framed`, except each length is written as a big-endian `u64`.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_framed_be<T: AsRef<[u8]>>(parts: &[T]) -> u64 {
+    hash64(frame_parts(parts, u64::to_be_bytes))
+}
+
+/// `FarmHash` 64-bit hash function for a sparse bitset, given by the indices of its set bits.
+///
+/// The indices are written in order as little-endian `u32`s and hashed with `hash64`,
+/// so the cost depends on the number of set bits rather than on the size of the bitset.
+/// The caller must sort and deduplicate the indices, otherwise the same set may produce
+/// different hashes; this is checked in debug builds.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_sparse(set_indices: &[u32]) -> u64 {
+    debug_assert!(set_indices.windows(2).all(|w| w[0] < w[1]),
+                  "indices must be sorted and deduplicated");
+
+    let mut buf = Vec::with_capacity(set_indices.len() * 4);
+
+    for i in set_indices {
+        buf.extend_from_slice(&i.to_le_bytes());
+    }
+
+    hash64(&buf)
+}
+
+/// `FarmHash` 64-bit hash function for a sequence of byte arrays, framed in a reusable buffer.
+///
+/// The parts are framed like `hash64_framed` into `scratch`, which is cleared first
+/// and keeps its capacity, and then hashed with `hash64_with_seed`;
+/// so hot loops framing many composite keys don't allocate once the buffer is large enough.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_framed_with_scratch<T: AsRef<[u8]>>(parts: &[T],
+                                                  seed: u64,
+                                                  scratch: &mut Vec<u8>)
+                                                  -> u64 {
+    frame_parts_into(parts, u64::to_le_bytes, scratch);
+
+    hash64_with_seed(scratch, seed)
+}
+
+/// `FarmHash` 64-bit hash function for the items of an iterator, without collecting them.
+///
+/// Each item is framed by its length like `hash64_framed`, and written in order
+/// into a `FarmHasher64`, so the result equals `hash64_framed` of the same sequence.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_iter_items<I: IntoIterator<Item = T>, T: AsRef<[u8]>>(iter: I) -> u64 {
+    let mut h = FarmHasher64::new();
+
+    for item in iter {
+        h.write_framed(item.as_ref());
+    }
+
+    h.finish()
+}
+
+/// An extension trait hashing all the items of an iterator of byte arrays.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::farm::{self, HashIterExt};
+///
+/// let lines = vec!["hello", "world"];
+///
+/// assert_eq!(lines.iter().farm_hash64(), farm::hash64_framed(&lines));
+/// ```
+#[cfg(feature = "alloc")]
+pub trait HashIterExt: Iterator + Sized {
+    /// `FarmHash` 64-bit hash function for the items, framed by their lengths,
+    /// see `hash64_iter_items`.
+    #[inline]
+    fn farm_hash64(self) -> u64
+        where Self::Item: AsRef<[u8]>
+    {
+        hash64_iter_items(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> HashIterExt for I {}
+
+/// The declared type of a field in a row schema, see `hash64_row`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    /// A boolean.
+    Bool = 1,
+    /// A signed integer.
+    Int = 2,
+    /// An unsigned integer.
+    UInt = 3,
+    /// A floating-point number.
+    Float = 4,
+    /// A UTF-8 string.
+    String = 5,
+    /// Raw bytes.
+    Bytes = 6,
+}
+
+/// `FarmHash` 64-bit hash function for a row of values described by a schema.
+///
+/// Each field is written as its one-byte `FieldType` tag followed by the value framed
+/// by its length like `hash64_framed`, so the result depends on both the declared
+/// types and the values: the same bytes declared as another type hash differently.
+///
+/// # Panics
+///
+/// Panics if `values` does not have the same length as `schema`.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_row(schema: &[FieldType], values: &[&[u8]]) -> u64 {
+    assert_eq!(schema.len(), values.len(), "values length must match schema");
+
+    let mut h = FarmHasher64::new();
+
+    for (&ty, value) in schema.iter().zip(values) {
+        h.write(&[ty as u8]);
+        h.write(&(value.len() as u64).to_le_bytes());
+        h.write(value);
+    }
+
+    h.finish()
+}
+
+#[cfg(feature = "alloc")]
+fn frame_parts<T: AsRef<[u8]>>(parts: &[T], encode: fn(u64) -> [u8; 8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    frame_parts_into(parts, encode, &mut buf);
+
+    buf
+}
+
+#[cfg(feature = "alloc")]
+fn frame_parts_into<T: AsRef<[u8]>>(parts: &[T], encode: fn(u64) -> [u8; 8], buf: &mut Vec<u8>) {
+    let len = parts.iter().map(|part| 8 + part.as_ref().len()).sum();
+
+    buf.clear();
+    buf.reserve(len);
+
+    for part in parts {
+        let part = part.as_ref();
+
+        buf.extend_from_slice(&encode(part.len() as u64));
+        buf.extend_from_slice(part);
+    }
+}
+
+/// A `FarmHash` 64-bit hasher namespacing all its hashes under a context, e.g. a tenant.
+///
+/// The seed is precomputed once from the fingerprint of the context,
+/// so each call costs the same as `hash64_with_seed`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::farm::ContextHasher;
+///
+/// let tenant = ContextHasher::new(b"tenant-1");
+///
+/// assert_eq!(tenant.hash64(b"key"), ContextHasher::new(b"tenant-1").hash64(b"key"));
+/// assert!(tenant.hash64(b"key") != ContextHasher::new(b"tenant-2").hash64(b"key"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ContextHasher {
+    seed: u64,
+}
+
+impl ContextHasher {
+    /// Constructs a new `ContextHasher` for the context.
+    #[inline]
+    pub fn new<T: AsRef<[u8]>>(context: T) -> Self {
+        ContextHasher { seed: fingerprint64(context) }
+    }
+
+    /// Returns the seed derived from the context.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// `FarmHash` 64-bit hash function for a byte array in the context.
+    ///
+    /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+    #[inline]
+    #[must_use]
+    pub fn hash64<T: AsRef<[u8]>>(&self, v: T) -> u64 {
+        hash64_with_seed(v, self.seed)
+    }
+}
+
+macro_rules! impl_hash64_combine {
+    ($name:ident, $arity:expr, $( $part:ident : $T:ident ),+) => (
+        /// `FarmHash` 64-bit combined hash of
+        #[doc = $arity]
+        /// byte arrays.
+        ///
+        /// Each part is hashed on its own with its length as the seed, and the part hashes
+        /// are chained with `combine64`, so different groupings of the same bytes produce
+        /// different results without allocating a concatenated buffer.
+        /// The parts are not length-framed: the result differs from `hash64_framed`,
+        /// which hashes the length-prefixed parts as one byte array.
+        ///
+        /// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+        #[inline]
+        #[must_use]
+        pub fn $name<$( $T: AsRef<[u8]> ),+>($( $part: $T ),+) -> u64 {
+            let h = 0;
+            $( let $part = $part.as_ref(); )+
+            $( let h = combine64(h, hash64_with_seed($part, $part.len() as u64)); )+
+            h
+        }
+    )
+}
+
+impl_hash64_combine!(hash64_combine2, "2", a: A, b: B);
+impl_hash64_combine!(hash64_combine3, "3", a: A, b: B, c: C);
+impl_hash64_combine!(hash64_combine4, "4", a: A, b: B, c: C, d: D);
+
+/// Hash the entries of an interned-string table with the `FarmHash` 64-bit hash function,
+/// masked to the low `bits` bits.
+///
+/// Returns the hash of each entry, and the index pairs `(i, j)` with `i < j`
+/// of the entries colliding under that width, in ascending order.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+///
+/// # Panics
+///
+/// Panics if `bits` is not in `1..=64`.
+#[cfg(feature = "alloc")]
+pub fn hash_table(entries: &[&str], bits: u32) -> (Vec<u64>, Vec<(usize, usize)>) {
+    assert!(bits > 0 && bits <= 64, "bits must be in 1..=64");
+
+    let mask = if bits == 64 { !0 } else { (1 << bits) - 1 };
+    let hashes = entries.iter().map(|entry| hash64(entry) & mask).collect::<Vec<u64>>();
+
+    let mut order = (0..hashes.len()).collect::<Vec<usize>>();
+
+    order.sort_by_key(|&i| (hashes[i], i));
+
+    let mut collisions = Vec::new();
+    let mut start = 0;
+
+    while start < order.len() {
+        let mut end = start + 1;
+
+        while end < order.len() && hashes[order[end]] == hashes[order[start]] {
+            end += 1;
+        }
+
+        for a in start..end {
+            for b in a + 1..end {
+                collisions.push((order[a], order[b]));
+            }
+        }
+
+        start = end;
+    }
+
+    collisions.sort();
+
+    (hashes, collisions)
+}
+
+/// Estimate the probability of at least one collision among `num_keys` random keys
+/// hashed to `output_bits` bits, with the birthday-bound approximation
+/// `1 - exp(-n * (n - 1) / 2^(bits + 1))`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::farm;
+///
+/// // is 64 bits enough for 10M keys?
+/// assert!(farm::birthday_collision_probability(10_000_000, 64) < 1e-5);
+/// ```
+#[cfg(feature = "std")]
+pub fn birthday_collision_probability(num_keys: u64, output_bits: u32) -> f64 {
+    let n = num_keys as f64;
+    let pairs = n * (n - 1.0) / 2.0;
+
+    if pairs <= 0.0 {
+        0.0
+    } else {
+        -(-pairs / 2_f64.powi(output_bits as i32)).exp_m1()
+    }
+}
+
+/// Fold a 128-bit value into 64 bits with the `FarmHash` 128-to-64 fingerprint,
+/// `Fingerprint(uint128_t)` in the C++ API.
+///
+/// This is the idiom to merge sub-hashes, e.g. of the fields of a composite key,
+/// and the result is forever-fixed. See `combine64` to merge two 64-bit values.
+#[inline]
+#[must_use]
+pub fn hash_128_to_64(x: u128) -> u64 {
+    x.fingerprint()
+}
+
+/// Combine two 64-bit hash values into one with the `FarmHash` 128-to-64 fingerprint.
+///
+/// This is `hash_128_to_64` of `a` as the high and `b` as the low 64 bits, so the result
+/// is order sensitive, `combine64(a, b) != combine64(b, a)` in general.
+#[inline]
+#[must_use]
+pub fn combine64(a: u64, b: u64) -> u64 {
+    hash_128_to_64(u128_from_parts(a, b))
+}
+
+/// Combine per-shard partial hashes into a global hash, preserving the shard identity.
+///
+/// Each `(shard_id, partial)` pair is first mixed with `combine64(shard_id, partial)`,
+/// then folded in order with `combine64`, starting from zero;
+/// so moving a partial to a different shard changes the result.
+/// The result is order sensitive too, sort the partials by shard id
+/// if they may arrive in any order.
+#[inline]
+#[must_use]
+pub fn combine_shards(partials: &[(u32, u64)]) -> u64 {
+    partials.iter()
+        .fold(0, |h, &(shard_id, partial)| combine64(h, combine64(shard_id as u64, partial)))
+}
+
+/// Combine a sequence of 64-bit hash values into one with the `FarmHash` 128-to-64 fingerprint.
+///
+/// The hashes are folded in order with `combine64`, starting from their number,
+/// so the result is order sensitive, and leading zero hashes aren't absorbed.
+/// Only the fingerprint path is used, so the result is forever-fixed.
+#[inline]
+#[must_use]
+pub fn combine(hashes: &[u64]) -> u64 {
+    hashes.iter().cloned().fold(hashes.len() as u64, combine64)
+}
+
+/// Combine a sequence of 128-bit hash values into one with the `FarmHash` 128-to-64 fingerprint.
+///
+/// Like `combine`, the hashes are folded in order, into two 64-bit lanes starting from
+/// their number and its complement. Each hash is folded into the high lane with
+/// `hash_128_to_64` of its value, and into the low lane with `hash_128_to_64` of its
+/// swapped halves, so both lanes depend on all of its bits. The result is forever-fixed.
+#[inline]
+#[must_use]
+pub fn combine128(hashes: &[u128]) -> u128 {
+    let (hi, lo) = hashes.iter()
+        .fold((hashes.len() as u64, !(hashes.len() as u64)), |(hi, lo), &h| {
+            (combine64(hi, hash_128_to_64(h)), combine64(lo, hash_128_to_64(h.rotate_left(64))))
+        });
+
+    u128_from_parts(hi, lo)
+}
+
+/// `FarmHash` 64-bit hash function for a large byte array, hashing fixed-size chunks in parallel.
+///
+/// `data` is split into `chunk` bytes long chunks (the last one may be shorter),
+/// each chunk is hashed with `hash64_with_seed`, and the chunk hashes are folded
+/// in index order with `combine64`, starting from `seed`.
+/// The result only depends on `data`, `chunk` and `seed`, not on the number of threads,
+/// but it differs from the single-pass `hash64_with_seed(data, seed)`.
+///
+/// The chunks are hashed on the `rayon` thread pool when the `rayon` feature is enabled,
+/// and sequentially otherwise.
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_parallel(data: &[u8], chunk: usize, seed: u64) -> u64 {
+    assert!(chunk > 0, "chunk size must be non-zero");
+
+    chunk_hashes(data, chunk, seed).into_iter().fold(seed, combine64)
+}
+
+#[cfg(all(feature = "alloc", feature = "rayon"))]
+fn chunk_hashes(data: &[u8], chunk: usize, seed: u64) -> Vec<u64> {
+    use rayon::prelude::*;
+
+    data.par_chunks(chunk).map(|c| hash64_with_seed(c, seed)).collect()
+}
+
+#[cfg(all(feature = "alloc", not(feature = "rayon")))]
+fn chunk_hashes(data: &[u8], chunk: usize, seed: u64) -> Vec<u64> {
+    data.chunks(chunk).map(|c| hash64_with_seed(c, seed)).collect()
+}
+
+/// `FarmHash` 64-bit hash function for a batch of independent byte arrays,
+/// e.g. the keys inserted into a Bloom filter.
+///
+/// The result holds `hash64(&items[i])` at index `i`. The items are hashed on the
+/// `rayon` thread pool when the `rayon` feature is enabled, and sequentially otherwise.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_batch<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<u64> {
+    batch_hashes(items)
+}
+
+#[cfg(all(feature = "alloc", feature = "rayon"))]
+fn batch_hashes<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<u64> {
+    use rayon::prelude::*;
+
+    items.par_iter().map(FarmHash64::hash).collect()
+}
+
+#[cfg(all(feature = "alloc", not(feature = "rayon")))]
+fn batch_hashes<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<u64> {
+    items.iter().map(FarmHash64::hash).collect()
+}
+
+/// `FarmHash` 64-bit fingerprints of a batch of byte arrays, e.g. the records of a hash index.
+///
+/// `out[i]` is set to `fingerprint64(&items[i])`, and the first bytes of the next item are
+/// prefetched while the current one is hashed, to overlap the memory accesses
+/// of small records. Exactly `items.len()` outputs are written, the rest of `out` is untouched.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `items`.
+pub fn fingerprint64_many<T: AsRef<[u8]>>(items: &[T], out: &mut [u64]) {
+    assert!(out.len() >= items.len(), "out must hold a fingerprint per item");
+
+    for (i, (item, h)) in items.iter().zip(out.iter_mut()).enumerate() {
+        if let Some(next) = items.get(i + 1) {
+            prefetch_read(next.as_ref());
+        }
+
+        *h = fingerprint64(item);
+    }
+}
+
+// A prefetch is only a hint, it never faults, even past the end of an empty slice.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+#[inline(always)]
+fn prefetch_read(bytes: &[u8]) {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{_mm_prefetch, _MM_HINT_T0};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+    unsafe { _mm_prefetch::<_MM_HINT_T0>(bytes.as_ptr() as *const i8) }
+}
+
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse")))]
+#[inline(always)]
+fn prefetch_read(_bytes: &[u8]) {}
+
+/// `FarmHash` 64-bit fingerprints of the overlapping shingles of a byte array, for MinHash.
+///
+/// Every `k` bytes long window of `data` is hashed with `fingerprint64`, in order,
+/// so the result is stable across platforms and holds `data.len() - k + 1` hashes,
+/// or none if `data` is shorter than `k`.
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn shingle_hashes(data: &[u8], k: usize) -> Vec<u64> {
+    assert!(k > 0, "shingle size must be non-zero");
+
+    data.windows(k).map(fingerprint64).collect()
+}
+
+/// An accumulator chaining the `FarmHash` 64-bit hash of byte arrays with `combine64`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::farm::{self, Accumulator};
+///
+/// let h = Accumulator::new().add(b"hello").add(b"world").finish();
+///
+/// assert_eq!(h, farm::combine64(farm::combine64(0, farm::hash64(b"hello")),
+///                               farm::hash64(b"world")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Accumulator(u64);
+
+impl Accumulator {
+    /// Constructs a new `Accumulator` starting from zero.
+    #[inline]
+    pub fn new() -> Self {
+        Accumulator(0)
+    }
+
+    /// Constructs a new `Accumulator` starting from the seed.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        Accumulator(seed)
+    }
+
+    /// Mixes the 64-bit hash of a byte array into the accumulator.
+    #[inline]
+    // `add` takes any byte array, which `std::ops::Add` can't express as one impl
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: AsRef<[u8]>>(self, v: T) -> Self {
+        Accumulator(combine64(self.0, hash64(v)))
+    }
+
+    /// Returns the accumulated hash value.
+    #[inline]
+    #[must_use]
+    pub fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+impl Fingerprint<u64> for u64 {
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        unsafe { ffi::farmhash_fingerprint_uint64(*self) }
+    }
+}
+
+impl Fingerprint<u64> for u128 {
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        unsafe { ffi::farmhash_fingerprint_uint128(to_uint128_c(*self)) }
+    }
+}
+
+/// `FarmHash` 64-bit hash function for an optional byte array.
+///
+/// A discriminant byte (`0` for `None`, `1` for `Some`) is hashed before the value,
+/// so `Some(b"")` and `None` produce different results.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_option<T: AsRef<[u8]>>(v: &Option<T>) -> u64 {
+    let mut h = FarmHasher64::new();
+    write_option(&mut h, v);
+    h.finish()
+}
+
+/// `FarmHash` 64-bit hash function for a `Result` of byte arrays.
+///
+/// A discriminant byte (`0` for `Ok`, `1` for `Err`) is hashed before the value,
+/// so `Ok(x)` and `Err(x)` produce different results.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash64_result<T: AsRef<[u8]>, E: AsRef<[u8]>>(v: &Result<T, E>) -> u64 {
+    let mut h = FarmHasher64::new();
+    write_result(&mut h, v);
+    h.finish()
+}
+
+/// `FarmHash` 128-bit hash function for an optional byte array.
+///
+/// See `hash64_option` for the discriminant encoding.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash128_option<T: AsRef<[u8]>>(v: &Option<T>) -> u128 {
+    let mut h = FarmHasher128::new();
+    write_option(&mut h, v);
+    h.finish_ext()
+}
+
+/// `FarmHash` 128-bit hash function for a `Result` of byte arrays.
+///
+/// See `hash64_result` for the discriminant encoding.
+///
+/// May change from time to time, may differ on different platforms, may differ depending on NDEBUG.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn hash128_result<T: AsRef<[u8]>, E: AsRef<[u8]>>(v: &Result<T, E>) -> u128 {
+    let mut h = FarmHasher128::new();
+    write_result(&mut h, v);
+    h.finish_ext()
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn write_option<H: Hasher, T: AsRef<[u8]>>(h: &mut H, v: &Option<T>) {
+    match *v {
+        None => h.write(&[0]),
+        Some(ref v) => {
+            h.write(&[1]);
+            h.write(v.as_ref());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn write_result<H: Hasher, T: AsRef<[u8]>, E: AsRef<[u8]>>(h: &mut H, v: &Result<T, E>) {
+    match *v {
+        Ok(ref v) => {
+            h.write(&[0]);
+            h.write(v.as_ref());
+        }
+        Err(ref e) => {
+            h.write(&[1]);
+            h.write(e.as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::Hasher;
+
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Serialize};
+    #[cfg(feature = "serde")]
+    use serde_json;
+
+    use hasher::{Fingerprint, FastHash, FastHasher, HasherExt};
+    use super::*;
+
+    #[test]
+    fn test_farmhash32() {
+        let h1 = FarmHash32::hash(b"hello");
+        let h2 = FarmHash32::hash_with_seed(b"hello", 123);
+        let h3 = FarmHash32::hash(b"helloworld");
+
+        assert!(h1 != 0);
+        assert!(h2 != 0);
+        assert!(h3 != 0);
+
+        let mut h = FarmHasher32::new();
+
+        h.write(b"hello");
+        assert_eq!(h.finish(), h1 as u64);
+
+        h.write(b"world");
+        assert_eq!(h.finish(), h3 as u64);
+    }
+
+    #[test]
+    fn test_farmhasher32_split_points() {
+        use rand::{self, Rng};
+
+        let mut rng = rand::thread_rng();
+
+        for &len in &[0, 1, 4, 12, 13, 24, 25, 64, 1000, 1 << 16] {
+            let data = rng.gen_iter::<u8>().take(len).collect::<Vec<u8>>();
+            let expected = FarmHash32::hash(&data) as u64;
+
+            for _ in 0..8 {
+                let mut h = FarmHasher32::new();
+                let mut rest = &data[..];
+
+                while !rest.is_empty() {
+                    let n = rng.gen_range(1, rest.len() + 1);
+
+                    h.write(&rest[..n]);
+                    rest = &rest[n..];
+                }
+
+                assert_eq!(h.finish(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_farmhash64() {
+        assert_eq!(FarmHash64::hash(b"hello"), 14403600180753024522);
+        assert_eq!(FarmHash64::hash_with_seed(b"hello", 123),
+                   6856739100025169098);
+        assert_eq!(FarmHash64::hash_with_seeds(b"hello", 123, 456),
+                   15077713332534145879);
+        assert_eq!(FarmHash64::hash(b"helloworld"), 1077737941828767314);
+        assert_eq!(u64::from_le_bytes(FarmHash64::hash_array(b"hello")), hash64(b"hello"));
+        assert_eq!(FarmHash64::hash_array(b"hello"),
+                   14403600180753024522_u64.to_le_bytes());
+
+        let mut h = FarmHasher64::new();
+
+        h.write(b"hello");
+        assert_eq!(h.finish(), 14403600180753024522);
+
+        h.write(b"world");
+        assert_eq!(h.finish(), 1077737941828767314);
+    }
+
+    #[test]
+    fn test_marker_derives() {
+        let markers = vec![format!("{:?}", FarmHash32 {}),
+                           format!("{:?}", FarmHash64::default()),
+                           format!("{:?}", FarmHash128 {}),
+                           format!("{:?}", FarmFingerprint64 {})];
+
+        assert_eq!(markers, ["FarmHash32", "FarmHash64", "FarmHash128", "FarmFingerprint64"]);
+        assert_eq!(FarmHash64 {}, FarmHash64::default());
+    }
+
+    #[test]
+    fn test_farmhasher_with_seed() {
+        fn seeded_hash<H: FastHasher>(seed: H::Seed, bytes: &[u8]) -> u64 {
+            let mut h = H::with_seed(seed);
+            h.write(bytes);
+            h.finish()
+        }
+
+        assert_eq!(seeded_hash::<FarmHasher64>(123, b"hello"), 6856739100025169098);
+        assert_eq!(seeded_hash::<FarmHasher64>(123, b"hello"),
+                   FarmHash64::hash_with_seed(b"hello", 123));
+        assert_eq!(seeded_hash::<FarmHasher32>(123, b"hello"),
+                   FarmHash32::hash_with_seed(b"hello", 123) as u64);
+
+        let mut h = FarmHasher128::with_seed((15212901187400903054_u128 << 64) | 456);
+        h.write(b"hello");
+        assert_eq!(h.finish_ext(),
+                   FarmHash128::hash_with_seed(b"hello",
+                                               (15212901187400903054_u128 << 64) | 456));
+    }
+
+    #[test]
+    fn test_farmhash64_slice_by_value() {
+        let data = b"hello world".to_vec();
+        let some_slice: &[u8] = &data[..5];
+
+        assert_eq!(FarmHash64::hash(some_slice), 14403600180753024522);
+        assert_eq!(FarmHash64::hash_with_seed(some_slice, 123), 6856739100025169098);
+        assert_eq!(hash64(some_slice), FarmHash64::hash(b"hello"));
+        assert_eq!(hash64(&data[..5]), hash64("hello"));
+        assert_eq!(hash64(data.clone()), hash64(b"hello world"));
+
+        assert_eq!(fingerprint64_salted(some_slice, some_slice),
+                   fingerprint64_salted(b"hello", b"hello"));
+        assert_eq!(ContextHasher::new(some_slice).hash64(some_slice),
+                   ContextHasher::new(b"hello").hash64(b"hello"));
+        assert_eq!(hash64_combine2(some_slice, some_slice), hash64_combine2(b"hello", b"hello"));
+        assert_eq!(hash64_combine3(some_slice, some_slice, some_slice),
+                   hash64_combine3(b"hello", b"hello", b"hello"));
+        assert_eq!(hash64_combine4(some_slice, some_slice, some_slice, some_slice),
+                   hash64_combine4(b"hello", b"hello", b"hello", b"hello"));
+        assert_eq!(Accumulator::new().add(some_slice).finish(),
+                   Accumulator::new().add(b"hello").finish());
+    }
+
+    #[test]
+    fn test_farmhash64_hashlike() {
+        let mut h = FarmHasher64::new();
+
+        "hello".hash(&mut h);
+        assert_eq!(hash64_hashlike("hello"), h.finish());
+        assert_eq!(hash64_hashlike("hello"), hash64(b"hello\xff"));
+        assert_eq!(hash64_hashlike(""), hash64(b"\xff"));
+        assert!(hash64_hashlike("hello") != hash64_str("hello"));
+    }
+
+    #[test]
+    fn test_farmhash64_raw() {
+        let data = b"hello".to_vec();
+
+        unsafe {
+            assert_eq!(hash64_raw(data.as_ptr(), data.len()), 14403600180753024522);
+            assert_eq!(hash64_raw(data.as_ptr(), data.len()), hash64(&data));
+            assert_eq!(hash64_raw(data.as_ptr(), 0), hash64(b""));
+        }
+    }
+
+    #[test]
+    fn test_farmhash64_str() {
+        assert_eq!(hash64_str("hello"), 14403600180753024522);
+        assert_eq!(hash64_str("hello"), hash64("hello"));
+        assert_eq!(hash64_str(&String::from("hello")), hash64("hello".as_bytes()));
+        assert_eq!(hash64_str("h\u{e9}llo"), hash64("h\u{e9}llo".as_bytes()));
+    }
+
+    #[test]
+    fn test_farmhasher64_write_framed() {
+        let framed = |parts: &[&[u8]]| {
+            let mut h = FarmHasher64::new();
+
+            for part in parts {
+                h.write_framed(part);
+            }
+
+            h.finish()
+        };
+
+        assert!(framed(&[b"a", b"bc"]) != framed(&[b"ab", b"c"]));
+        assert_eq!(framed(&[b"a", b"bc"]), hash64_framed(&[&b"a"[..], b"bc"]));
+        assert_eq!(framed(&[b"hello"]), hash64(b"\x05\0\0\0\0\0\0\0hello"));
+    }
+
+    #[test]
+    fn test_farmhasher_extend() {
+        let mut h = FarmHasher64::new();
+        h.extend(b"hello".iter().copied());
+        assert_eq!(h.finish(), 14403600180753024522);
+
+        let mut h = FarmHasher128::new();
+        h.extend(vec![&b"hello"[..], b"world"]);
+        assert_eq!(h.finish_ext(), FarmHash128::hash(b"helloworld"));
+    }
+
+    #[test]
+    fn test_farmhasher_by_mut_ref() {
+        fn hash_each<H: Hasher>(mut state: H, values: &[u32]) -> u64 {
+            for v in values {
+                v.hash(&mut state);
+            }
+
+            state.finish()
+        }
+
+        let values: Vec<u32> = vec![1, 2, 3];
+        let bytes = values.iter().flat_map(|v| v.to_ne_bytes()).collect::<Vec<u8>>();
+        let mut h64 = FarmHasher64::new();
+        let mut h128 = FarmHasher128::new();
+
+        assert_eq!(hash_each(&mut h64, &values), FarmHash64::hash(&bytes));
+        assert_eq!(h64.finish(), FarmHash64::hash(&bytes));
+        assert_eq!(hash_each(&mut h128, &values), FarmHash128::hash(&bytes) as u64);
+        assert_eq!(h128.finish_ext(), FarmHash128::hash(&bytes));
+    }
+
+    #[test]
+    fn test_farmhasher64_with_capacity() {
+        fn reallocations(mut h: FarmHasher64) -> (usize, u64) {
+            let chunk = [0x5a_u8; 4096];
+            let mut capacity = h.capacity();
+            let mut n = 0;
+
+            for _ in 0..256 {
+                h.write(&chunk);
+
+                if h.capacity() != capacity {
+                    capacity = h.capacity();
+                    n += 1;
+                }
+            }
+
+            (n, h.finish())
+        }
+
+        let h = FarmHasher64::with_capacity(1 << 20);
+
+        assert!(h.capacity() >= 1 << 20);
+
+        let (presized, h1) = reallocations(h);
+        let (growing, h2) = reallocations(FarmHasher64::new());
+
+        assert_eq!(presized, 0);
+        assert!(growing > 0);
+        assert_eq!(h1, h2);
+        assert_eq!(h1, hash64(&[0x5a_u8; 1 << 20][..]));
+    }
+
+    #[test]
+    fn test_farmhasher64_hash_slices() {
+        assert_eq!(FarmHasher64::hash_slices(&[]), FarmHash64::hash(b""));
+        assert_eq!(FarmHasher64::hash_slices(&[b""]), FarmHash64::hash(b""));
+        assert_eq!(FarmHasher64::hash_slices(&[b"hello"]), FarmHash64::hash(b"hello"));
+        assert_eq!(FarmHasher64::hash_slices(&[b"hello", b"", b"world"]),
+                   FarmHash64::hash(b"helloworld"));
+        assert_eq!(FarmHasher64::hash_slices(&[b"hel", b"lowor", b"ld"]),
+                   FarmHash64::hash(b"helloworld"));
+    }
+
+    #[test]
+    fn test_farmhash64_dispatched() {
+        let h = FarmHash64::hash_dispatched(b"hello");
+
+        assert_eq!(FarmHash64::hash_dispatched(b"hello"), h);
+        assert!(FarmHash64::hash_dispatched(b"world") != h);
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        unsafe {
+            let portable = ffi::farmhash64_portable(b"hello".as_ptr() as *const i8, 5);
+            let sse42 = ffi::farmhash64_sse42(b"hello".as_ptr() as *const i8, 5);
+
+            assert!(h == portable || h == sse42);
+        }
+    }
+
+    #[test]
+    fn test_farm_build_hasher() {
+        use std::collections::HashMap;
+        use std::hash::BuildHasher;
+
+        let mut map: HashMap<String, u32, FarmBuildHasher> = HashMap::default();
+
+        map.insert("hello".to_owned(), 1);
+        map.insert("world".to_owned(), 2);
+
+        assert_eq!(map.get("hello"), Some(&1));
+        assert_eq!(map.get("world"), Some(&2));
+        assert_eq!(map.get("foobar"), None);
+
+        let s = FarmBuildHasher::default();
+
+        assert_eq!(s.seed(), 0);
+
+        let mut h = s.build_hasher();
+
+        h.write(b"hello");
+        assert_eq!(h.finish(), FarmHash64::hash_with_seed(b"hello", 0));
+
+        let s = FarmBuildHasher::random();
+        let mut h1 = s.build_hasher();
+        let mut h2 = s.build_hasher();
+
+        h1.write(b"hello");
+        h2.write(b"hello");
+        assert_eq!(h1.finish(), h2.finish());
+
+        let mut map = HashMap::with_hasher(s);
+
+        map.insert("hello".to_owned(), 1);
+        assert_eq!(map.get("hello"), Some(&1));
+    }
+
+    #[test]
+    fn test_farmhash128() {
+        assert_eq!(FarmHash128::hash(b"hello"),
+                   (14545675544334878584_u128 << 64) | 15888401098353921598);
+        assert_eq!(FarmHash128::hash_with_seed(b"hello", 123),
+                   (15212901187400903054_u128 << 64) | 13320390559359511083);
+        assert_eq!(FarmHash128::hash(b"helloworld"),
+                   (16066658700231169910_u128 << 64) | 1119455499735156801);
+
+        let mut out = [0; 16];
+
+        FarmHash128::hash_into(b"helloworld", &mut out);
+        assert_eq!(u64::from_le_bytes([out[0], out[1], out[2], out[3],
+                                       out[4], out[5], out[6], out[7]]),
+                   FarmHash128::hash(b"helloworld") as u64);
+        assert_eq!(u64::from_le_bytes([out[8], out[9], out[10], out[11],
+                                       out[12], out[13], out[14], out[15]]),
+                   (FarmHash128::hash(b"helloworld") >> 64) as u64);
+        assert_eq!(out, FarmHash128::hash(b"helloworld").to_le_bytes());
+        assert_eq!(FarmHash128::hash_array(b"helloworld"), out);
+        assert_eq!(u128::from_le_bytes(FarmHash128::hash_array(b"hello")), hash128(b"hello"));
+
+        let mut seed = [0; 16];
+
+        seed[0] = 123;
+        assert_eq!(FarmHash128::hash_with_seed_bytes(b"hello", &seed),
+                   (15212901187400903054_u128 << 64) | 13320390559359511083);
+
+        seed[8] = 1;
+        assert_eq!(FarmHash128::hash_with_seed_bytes(b"hello", &seed),
+                   FarmHash128::hash_with_seed(b"hello", (1_u128 << 64) | 123));
+
+        let mut h = FarmHasher128::new();
+
+        h.write(b"hello");
+        assert_eq!(h.finish_ext(),
+                   (14545675544334878584_u128 << 64) | 15888401098353921598);
+
+        h.write(b"world");
+        assert_eq!(h.finish_ext(),
+                   (16066658700231169910_u128 << 64) | 1119455499735156801);
+    }
+
+    #[test]
+    fn test_hash_all() {
+        assert_eq!(hash_all(b"hello"),
+                   HashTriple {
+                       h32: 3386678999,
+                       h64: 15888401098353921598,
+                       h128: (14545675544334878584_u128 << 64) | 15888401098353921598,
+                   });
+        assert_eq!(hash_all(b"helloworld").h128, hash128(b"helloworld"));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint32(b"hello word"), 4146030890);
+        assert_eq!(fingerprint64(b"hello word"), 2862784602449412590_u64);
+        assert_eq!(fingerprint128(b"hello word"),
+                   (3993975538242800734_u128 << 64) | 12454188156902618296);
+        assert_eq!(123_u64.fingerprint(), 4781265650859502840);
+        assert_eq!(123_u128.fingerprint(), 4011577241381678309);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_run_selftest() {
+        assert_eq!(run_selftest(), Ok(()));
+    }
+
+    #[test]
+    fn test_hash64_cc() {
+        assert_eq!(hash64_cc(b"hello word"), 2862784602449412590);
+        assert_eq!(hash64_cc(b"hello"), 13009744463427800296);
+        assert_eq!(hash64_cc(b"hello"), fingerprint64(b"hello"));
+    }
+
+    #[test]
+    fn test_uint128_c_conversion() {
+        let v = (123_u128 << 64) | 456;
+        let c = to_uint128_c(v);
+
+        assert_eq!((c.a, c.b), (456, 123));
+        assert_eq!(from_uint128_c(c), v);
+        assert_eq!(from_uint128_c(ffi::uint128_c_t { a: 1, b: 0 }), 1);
+    }
+
+    #[test]
+    fn test_uint128_c_layout() {
+        assert_eq!(mem::size_of::<ffi::uint128_c_t>(), 16);
+        assert_eq!(mem::align_of::<ffi::uint128_c_t>(), 8);
+        assert_eq!(FarmHash128::hash(b"hello"),
+                   (14545675544334878584_u128 << 64) | 15888401098353921598);
+    }
+
+    #[cfg(feature = "const-hash")]
+    #[test]
+    fn test_fingerprint64_const() {
+        const HELLO_WORD: u64 = fingerprint64_const(b"hello word");
+
+        assert_eq!(HELLO_WORD, 2862784602449412590_u64);
+
+        let data = (0..300).map(|i| (i * 131 + 7) as u8).collect::<Vec<u8>>();
+
+        for len in 0..data.len() {
+            assert_eq!(fingerprint64_const(&data[..len]), fingerprint64(&data[..len]));
+        }
+
+        for s in &["", "a", "hello", "hello world", "the quick brown fox jumps over the lazy dog"] {
+            assert_eq!(fingerprint64_const(s.as_bytes()), fingerprint64(s));
+        }
+    }
+
+    #[test]
+    fn test_farm_fingerprint() {
+        fn persist<H: StableHash<Value = u64>>(v: &[u8]) -> u64 {
+            H::hash(v)
+        }
+
+        assert_eq!(FarmFingerprint32::hash(b"hello word"), 4146030890);
+        assert_eq!(FarmFingerprint64::hash(b"hello word"), 2862784602449412590_u64);
+        assert_eq!(FarmFingerprint128::hash(b"hello word"),
+                   (3993975538242800734_u128 << 64) | 12454188156902618296);
+        assert_eq!(FarmFingerprint64::hash_with_seed(b"hello word", ()),
+                   fingerprint64(b"hello word"));
+        assert_eq!(persist::<FarmFingerprint64>(b"hello word"), 2862784602449412590_u64);
+
+        let mut h = FarmFingerprintHasher64::new();
+
+        h.write(b"hello");
+        h.write(b" word");
+        assert_eq!(h.finish(), 2862784602449412590_u64);
+
+        let mut h = FarmFingerprintHasher128::new();
+
+        h.write(b"hello word");
+        assert_eq!(h.finish_ext(),
+                   (3993975538242800734_u128 << 64) | 12454188156902618296);
     }
 
     #[test]
-    fn test_fingerprint() {
-        assert_eq!(fingerprint32(b"hello word"), 4146030890);
-        assert_eq!(fingerprint64(b"hello word"), 2862784602449412590_u64);
-        assert_eq!(fingerprint128(b"hello word"),
-                   u128::from_parts(3993975538242800734, 12454188156902618296));
-        assert_eq!(123_u64.fingerprint(), 4781265650859502840);
-        assert_eq!(u128::new(123).fingerprint(), 4011577241381678309);
+    fn test_fingerprint_newtypes() {
+        let fp64 = Fingerprint64::of(b"hello word");
+        let fp128 = Fingerprint128::of(b"hello word");
+
+        assert_eq!(fp64, Fingerprint64(2862784602449412590));
+        assert_eq!(fp128, Fingerprint128(fingerprint128(b"hello word")));
+        assert_eq!(Fingerprint64(0x123).to_string(), "0000000000000123");
+        assert_eq!(format!("{:x}", Fingerprint128(1 << 64)), "00000000000000010000000000000000");
+        assert_eq!(fp64.to_string().parse(), Ok(fp64));
+        assert_eq!(fp128.to_string().parse(), Ok(fp128));
+        assert!("hello".parse::<Fingerprint64>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_fingerprint_serde() {
+        let fp64 = Fingerprint64(2862784602449412590);
+        let fp128 = Fingerprint128(u128::MAX);
+
+        assert_eq!(serde_json::to_string(&fp64).unwrap(), "2862784602449412590");
+        assert_eq!(serde_json::from_str::<Fingerprint64>("2862784602449412590").unwrap(), fp64);
+        assert_eq!(serde_json::to_string(&fp128).unwrap(),
+                   "340282366920938463463374607431768211455");
+        assert_eq!(serde_json::from_str::<Fingerprint128>("340282366920938463463374607431768211455")
+                       .unwrap(),
+                   fp128);
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Hex {
+            #[serde(with = "hex")]
+            h64: Fingerprint64,
+            #[serde(with = "hex")]
+            h128: Fingerprint128,
+        }
+
+        let v = Hex {
+            h64: Fingerprint64(0x123),
+            h128: Fingerprint128(1 << 64),
+        };
+        let json = serde_json::to_string(&v).unwrap();
+
+        assert_eq!(json,
+                   r#"{"h64":"0000000000000123","h128":"00000000000000010000000000000000"}"#);
+        assert_eq!(serde_json::from_str::<Hex>(&json).unwrap(), v);
+        assert!(serde_json::from_str::<Hex>(r#"{"h64":"xyz","h128":"0"}"#).is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_value() {
+        assert_eq!(fingerprint_value("hello world"), hash64(b"hello world\xff"));
+        assert_eq!(fingerprint_value(&"hello world"), fingerprint_value("hello world"));
+        assert_eq!(fingerprint_value(&123_u64), hash64(123_u64.to_ne_bytes()));
+        assert_eq!(fingerprint_value(&(1_u8, 2_u8)), hash64(b"\x01\x02"));
+        assert!(fingerprint_value("hello world") != fingerprint64(b"hello world"));
+    }
+
+    #[test]
+    fn test_hash64_float() {
+        assert_eq!(hash64_f64(0.0), hash64_f64(-0.0));
+        assert_eq!(hash64_f64(f64::NAN),
+                   hash64_f64(f64::from_bits(0x7ff8_0000_0000_0001)));
+        assert_eq!(hash64_f64(f64::NAN),
+                   hash64_f64(f64::from_bits(0xfff0_0000_0000_0001)));
+        assert_eq!(hash64_f64(1.5), hash64(1.5_f64.to_bits().to_le_bytes()));
+        assert!(hash64_f64(1.0) != hash64_f64(2.0));
+        assert!(hash64_f64(0.0) != hash64_f64(f64::NAN));
+
+        assert_eq!(hash64_f32(0.0), hash64_f32(-0.0));
+        assert_eq!(hash64_f32(f32::NAN),
+                   hash64_f32(f32::from_bits(0x7fc0_0001)));
+        assert_eq!(hash64_f32(f32::NAN), hash64_f32(f32::from_bits(0xff80_0001)));
+        assert!(hash64_f32(1.0) != hash64_f32(2.0));
+        assert!(hash64_f32(1.0) != hash64_f64(1.0));
+    }
+
+    #[test]
+    fn test_hash64_debug() {
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        assert_eq!(hash64_debug(&Point { x: 1, y: 2 }),
+                   hash64(b"Point { x: 1, y: 2 }"));
+        assert_eq!(hash64_debug(&Point { x: 1, y: 2 }),
+                   hash64_debug(&Point { x: 1, y: 2 }));
+        assert!(hash64_debug(&Point { x: 1, y: 2 }) != hash64_debug(&Point { x: 2, y: 1 }));
+
+        assert_eq!(hash64_debug(&Some(123)), hash64_debug(&Some(123u8)));
+        assert_eq!(hash64_debug("hello"), hash64(b"\"hello\""));
+        assert!(hash64_debug(&vec![1, 2, 3]) != hash64_debug(&[1, 2]));
+    }
+
+    #[test]
+    fn test_hash128_batch_soa() {
+        let inputs: Vec<&[u8]> = vec![b"", b"hello", b"world", b"hello world"];
+        let mut highs = vec![0; inputs.len()];
+        let mut lows = vec![0; inputs.len()];
+
+        hash128_batch_soa(&inputs, &mut highs, &mut lows);
+
+        for (i, input) in inputs.iter().enumerate() {
+            assert_eq!(highs[i], (hash128(input) >> 64) as u64);
+            assert_eq!(lows[i], hash128(input) as u64);
+        }
+
+        hash128_batch_soa(&[], &mut [], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hash128_batch_soa_length_mismatch() {
+        hash128_batch_soa(&[b"hello"], &mut [0; 1], &mut [0; 2]);
+    }
+
+    #[test]
+    fn test_hash64_fixed() {
+        let id = 123_456_789u64.to_le_bytes();
+
+        assert_eq!(hash64_fixed(&id, 0), hash64_with_seed(&id[..], 0));
+        assert_eq!(hash64_fixed(&id, 123), hash64_with_seed(&id[..], 123));
+        assert_eq!(hash64_fixed(b"hello", 123), hash64_with_seed(b"hello", 123));
+        assert_eq!(hash64_fixed(&[], 123), hash64_with_seed(b"", 123));
+    }
+
+    #[test]
+    fn test_hash64_validate_utf8() {
+        assert_eq!(hash64_validate_utf8(b""), Ok(hash64(b"")));
+        assert_eq!(hash64_validate_utf8(b"hello world"), Ok(hash64(b"hello world")));
+        assert_eq!(hash64_validate_utf8("h\u{e9}llo \u{4e16}\u{754c}".as_bytes()),
+                   Ok(hash64("h\u{e9}llo \u{4e16}\u{754c}")));
+
+        // a long input of multi-byte characters
+        let s = "\u{1f600}".repeat(3000);
+
+        assert_eq!(hash64_validate_utf8(s.as_bytes()), Ok(hash64(&s)));
+
+        let err = hash64_validate_utf8(b"hello \xffworld").unwrap_err();
+
+        assert_eq!(err.valid_up_to(), 6);
+        assert_eq!(err.error_len(), Some(1));
+
+        let mut v = s.clone().into_bytes();
+
+        v.pop();
+
+        let err = hash64_validate_utf8(&v).unwrap_err();
+
+        assert_eq!(err.valid_up_to(), s.len() - 4);
+        assert_eq!(err.error_len(), None);
+
+        let mut v = s.into_bytes();
+
+        v[5000] = b'\xff';
+
+        assert_eq!(hash64_validate_utf8(&v).unwrap_err(), str::from_utf8(&v).unwrap_err());
+    }
+
+    #[test]
+    fn test_jitter_fraction() {
+        assert_eq!(jitter_fraction(b"hello", 123), jitter_fraction(b"hello", 123));
+        assert!(jitter_fraction(b"hello", 123) != jitter_fraction(b"world", 123));
+        assert!(jitter_fraction(b"hello", 123) != jitter_fraction(b"hello", 456));
+
+        let n = 10000;
+        let mut sum = 0.0;
+
+        for i in 0..n {
+            let jitter = jitter_fraction(format!("key-{}", i).as_bytes(), 123);
+
+            assert!((-1.0..1.0).contains(&jitter));
+
+            sum += jitter;
+        }
+
+        assert!((sum / n as f64).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_hash64_permuted() {
+        let data = b"hello world, the quick brown fox jumps over the lazy dog";
+
+        assert_eq!(hash64_permuted(data, 123), hash64_permuted(data, 123));
+        assert!(hash64_permuted(data, 123) != hash64_permuted(data, 456));
+        assert!(hash64_permuted(data, 123) != hash64(data));
+        assert!(hash64_permuted(b"ab", 123) != hash64_permuted(b"ba", 123));
+
+        // a permutation of identical bytes is the identity
+        assert_eq!(hash64_permuted(b"aaaa", 123), hash64(b"aaaa"));
+        assert_eq!(hash64_permuted(b"a", 123), hash64(b"a"));
+        assert_eq!(hash64_permuted(b"", 123), hash64(b""));
+    }
+
+    #[test]
+    fn test_hash128_git_style() {
+        assert_eq!(hash128_git_style("blob", b"hello world\n"),
+                   (10157039773667414122_u128 << 64) | 16440287202309140189);
+        assert_eq!(hash128_git_style("blob", b"hello world\n"),
+                   fingerprint128(b"blob 12\0hello world\n"));
+        assert!(hash128_git_style("blob", b"") != hash128_git_style("tree", b""));
+    }
+
+    #[test]
+    fn test_combine_shards() {
+        let partials = [(0, hash64(b"hello")), (1, hash64(b"world")), (2, hash64(b"!"))];
+
+        assert_eq!(combine_shards(&[]), 0);
+        assert_eq!(combine_shards(&partials), combine_shards(&partials));
+        assert_eq!(combine_shards(&partials[..1]),
+                   combine64(0, combine64(0, hash64(b"hello"))));
+
+        let mut moved = partials;
+
+        moved[1].0 = 3;
+        assert!(combine_shards(&moved) != combine_shards(&partials));
+
+        let mut swapped = partials;
+
+        swapped[0].1 = partials[1].1;
+        swapped[1].1 = partials[0].1;
+        assert!(combine_shards(&swapped) != combine_shards(&partials));
+    }
+
+    #[test]
+    fn test_hash64_parallel() {
+        let data = (0..100_000).map(|i| i as u8).collect::<Vec<u8>>();
+        let expected = data.chunks(4096)
+            .fold(123, |acc, c| combine64(acc, hash64_with_seed(c, 123)));
+
+        assert_eq!(hash64_parallel(&data, 4096, 123), expected);
+        assert_eq!(hash64_parallel(b"", 4096, 123), 123);
+        assert!(hash64_parallel(&data, 4096, 123) != hash64_parallel(&data, 1024, 123));
+        assert!(hash64_parallel(&data, 4096, 123) != hash64_parallel(&data, 4096, 456));
+    }
+
+    #[test]
+    fn test_hash64_batch() {
+        let items = (0..10_000).map(|i| format!("key-{}", i)).collect::<Vec<String>>();
+        let hashes = hash64_batch(&items);
+
+        assert_eq!(hashes.len(), items.len());
+
+        for (item, &h) in items.iter().zip(&hashes) {
+            assert_eq!(h, hash64(item));
+        }
+
+        assert_eq!(hash64_batch(&[b"hello"]), [14403600180753024522]);
+        assert!(hash64_batch::<&[u8]>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint64_many() {
+        let items = (0..10_000).map(|i| format!("record-{}", i)).collect::<Vec<String>>();
+        let mut out = vec![0; items.len() + 1];
+
+        out[items.len()] = 123;
+        fingerprint64_many(&items, &mut out);
+
+        for (item, &h) in items.iter().zip(&out) {
+            assert_eq!(h, fingerprint64(item));
+        }
+
+        assert_eq!(out[items.len()], 123);
+
+        let mut out = [0];
+
+        fingerprint64_many(&[b"hello word"], &mut out);
+        assert_eq!(out, [2862784602449412590]);
+
+        fingerprint64_many::<&[u8]>(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "out must hold a fingerprint per item")]
+    fn test_fingerprint64_many_short_out() {
+        fingerprint64_many(&[b"hello", b"world"], &mut [0]);
+    }
+
+    #[test]
+    fn test_shingle_hashes() {
+        let data = b"the quick brown fox";
+        let shingles = shingle_hashes(data, 4);
+
+        assert_eq!(shingles.len(), data.len() - 4 + 1);
+
+        for (i, &h) in shingles.iter().enumerate() {
+            assert_eq!(h, fingerprint64(&data[i..i + 4]));
+        }
+
+        assert_eq!(shingle_hashes(b"hello word", 10), [2862784602449412590]);
+        assert!(shingle_hashes(b"hello", 6).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "shingle size must be non-zero")]
+    fn test_shingle_hashes_zero_size() {
+        let _ = shingle_hashes(b"hello", 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_hash64_parallel_thread_count() {
+        use rayon::ThreadPoolBuilder;
+
+        let data = (0..100_000).map(|i| i as u8).collect::<Vec<u8>>();
+        let hash_with_threads = |n| {
+            ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .unwrap()
+                .install(|| hash64_parallel(&data, 1000, 123))
+        };
+
+        assert_eq!(hash_with_threads(1), hash_with_threads(4));
+        assert_eq!(hash_with_threads(1), hash64_parallel(&data, 1000, 123));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hash64_parallel_zero_chunk() {
+        let _ = hash64_parallel(b"hello", 0, 123);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_hash64_file() {
+        use std::env;
+        use std::fs;
+        use std::process;
+
+        let path = env::temp_dir().join(format!("fasthash-test-{}", process::id()));
+        let data = (0..100_000).map(|i| (i * 7) as u8).collect::<Vec<u8>>();
+
+        fs::write(&path, &data).unwrap();
+
+        let h = hash64_file(&path).unwrap();
+
+        assert_eq!(h, hash64(fs::read(&path).unwrap()));
+        assert_eq!(h, hash64(&data));
+
+        fs::write(&path, b"").unwrap();
+        assert_eq!(hash64_file(&path).unwrap(), hash64(b""));
+
+        fs::remove_file(&path).unwrap();
+        assert!(hash64_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_hash64_cstr_truncated() {
+        assert_eq!(hash64_cstr_truncated(b"hello\0world"), hash64(b"hello"));
+        assert!(hash64_cstr_truncated(b"hello\0world") != hash64(b"hello\0world"));
+        assert_eq!(hash64_cstr_truncated(b"hello\0"), hash64(b"hello"));
+        assert_eq!(hash64_cstr_truncated(b"hello"), hash64(b"hello"));
+        assert_eq!(hash64_cstr_truncated(b"\0hello"), hash64(b""));
+        assert_eq!(hash64_cstr_truncated(b""), hash64(b""));
+    }
+
+    #[test]
+    fn test_hash64_with_popcount() {
+        assert_eq!(hash64_with_popcount(b"hello", 123), (6856739100025169098, 25));
+
+        for data in &[&b""[..], b"a", b"hello", b"helloworld", &[0xff; 100]] {
+            let (h, popcount) = hash64_with_popcount(data, 456);
+
+            assert_eq!(h, hash64_with_seed(data, 456));
+            assert_eq!(popcount, h.count_ones());
+        }
+    }
+
+    #[test]
+    fn test_hash_to_bits() {
+        assert_eq!(hash_to_bits(b"hello", 64), hash64(b"hello"));
+        assert_eq!(hash_to_bits(b"hello", 40), hash64(b"hello") >> 24);
+
+        for &bits in &[1, 4, 13, 40, 63] {
+            for i in 0..1000_u32 {
+                assert!(hash_to_bits(&i.to_le_bytes(), bits) < 1 << bits);
+            }
+        }
+
+        for &bits in &[1_u32, 4] {
+            let mut buckets = vec![0; 1 << bits];
+            let samples = 1000 << bits;
+
+            for i in 0..samples as u32 {
+                buckets[hash_to_bits(&i.to_le_bytes(), bits) as usize] += 1;
+            }
+
+            for &n in &buckets {
+                assert!(n > 850 && n < 1150, "bucket of {} samples", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash64_unpadded() {
+        assert_eq!(hash64_unpadded(b"hello\0\0\0", 0), hash64(b"hello"));
+        assert_eq!(hash64_unpadded(b"hello", 0), hash64(b"hello"));
+        assert_eq!(hash64_unpadded(b"hello   ", b' '), hash64_unpadded(b"hello", b' '));
+        assert_eq!(hash64_unpadded(b"he llo  ", b' '), hash64(b"he llo"));
+        assert_eq!(hash64_unpadded(b"\0\0\0", 0), hash64(b""));
+        assert_eq!(hash64_unpadded(b"", 0), hash64(b""));
+        assert!(hash64_unpadded(b"hello\0\0", 0) != hash64(b"hello\0\0"));
+    }
+
+    #[test]
+    fn test_hash64_typed() {
+        let h = hash64_typed(b"hello");
+
+        assert_eq!(h, FarmHash64Value(hash64(b"hello")));
+        assert_eq!(*h, hash64(b"hello"));
+        assert_eq!(*h % 7, hash64(b"hello") % 7);
+        assert!(h != hash64_typed(b"world"));
+    }
+
+    #[test]
+    fn test_is_reproducible_build() {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        unsafe {
+            if cfg!(feature = "sse42") {
+                assert!(!is_reproducible_build());
+            }
+
+            // `farmhash64_te` and `farmhash64_xo` only differ from 512 bytes on
+            let data = (0..1024).map(|i| (i * 131 + 7) as u8).collect::<Vec<_>>();
+            let portable = ffi::farmhash64_portable(data.as_ptr() as *const i8, data.len());
+            let sse42 = ffi::farmhash64_sse42(data.as_ptr() as *const i8, data.len());
+
+            assert!(portable != sse42);
+            assert_eq!(hash64(&data),
+                       if is_reproducible_build() { portable } else { sse42 });
+        }
+    }
+
+    #[test]
+    fn test_version_tag() {
+        assert_eq!(version_tag(), version_tag());
+        assert!(version_tag() != 0);
+
+        if cfg!(target_endian = "little") {
+            assert_eq!(version_tag(),
+                       if is_reproducible_build() {
+                           8086377086895719375
+                       } else {
+                           2290857741081151077
+                       });
+        }
+    }
+
+    #[test]
+    fn test_fingerprint64_salted() {
+        assert_eq!(fingerprint64_salted(b"x", b"hello"), 15040433285385218528);
+        assert_eq!(fingerprint64_salted(b"deployment", b"hello world"),
+                   67967386726830950);
+        assert_eq!(fingerprint64_salted(b"deployment", b"hello world"),
+                   fingerprint64(b"\x0a\x00\x00\x00\x00\x00\x00\x00deploymenthello world"));
+        assert!(fingerprint64_salted(b"deployment", b"hello world") !=
+                fingerprint64_salted(b"deployment-2", b"hello world"));
+        assert!(fingerprint64_salted(b"ab", b"c") != fingerprint64_salted(b"a", b"bc"));
+    }
+
+    #[test]
+    fn test_hash64_framed() {
+        assert_eq!(hash64_framed(&["ab", "c"]),
+                   hash64(b"\x02\x00\x00\x00\x00\x00\x00\x00ab\x01\x00\x00\x00\x00\x00\x00\x00c"));
+        assert_eq!(hash64_framed_be(&["ab", "c"]),
+                   hash64(b"\x00\x00\x00\x00\x00\x00\x00\x02ab\x00\x00\x00\x00\x00\x00\x00\x01c"));
+        assert!(hash64_framed(&["ab", "c"]) != hash64_framed(&["a", "bc"]));
+        assert!(hash64_framed(&["ab", "c"]) != hash64_framed_be(&["ab", "c"]));
+
+        let empty: [&[u8]; 0] = [];
+
+        assert_eq!(hash64_framed(&empty), hash64(b""));
+    }
+
+    #[test]
+    fn test_hash64_sparse() {
+        let dense = |bits: &[u8]| {
+            (0..bits.len() as u32 * 8)
+                .filter(|&i| bits[i as usize / 8] & (1 << (i % 8)) != 0)
+                .collect::<Vec<u32>>()
+        };
+
+        assert_eq!(dense(&[0b0000_0101, 0b1000_0000]), [0, 2, 15]);
+        assert_eq!(hash64_sparse(&dense(&[0b0000_0101, 0b1000_0000])),
+                   hash64(b"\0\0\0\0\x02\0\0\0\x0f\0\0\0"));
+        assert_eq!(hash64_sparse(&[0, 2, 15]), hash64_sparse(&[0, 2, 15]));
+        assert!(hash64_sparse(&dense(&[0b0000_0101, 0b1000_0000])) !=
+                hash64_sparse(&dense(&[0b0000_0101, 0b0100_0000])));
+        assert_eq!(hash64_sparse(&[]), hash64(b""));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "indices must be sorted and deduplicated")]
+    fn test_hash64_sparse_unsorted() {
+        let _ = hash64_sparse(&[2, 0]);
+    }
+
+    #[test]
+    fn test_hash64_framed_with_scratch() {
+        let mut scratch = Vec::new();
+
+        assert_eq!(hash64_framed_with_scratch(&["hello", "world"], 123, &mut scratch),
+                   hash64_with_seed(frame_parts(&["hello", "world"], u64::to_le_bytes), 123));
+        assert_eq!(scratch, frame_parts(&["hello", "world"], u64::to_le_bytes));
+
+        let capacity = scratch.capacity();
+        let ptr = scratch.as_ptr();
+
+        for _ in 0..10 {
+            assert_eq!(hash64_framed_with_scratch(&["hell", "o"], 456, &mut scratch),
+                       hash64_with_seed(frame_parts(&["hell", "o"], u64::to_le_bytes), 456));
+            assert_eq!(scratch.capacity(), capacity);
+            assert_eq!(scratch.as_ptr(), ptr);
+        }
+
+        assert!(hash64_framed_with_scratch(&["ab", "c"], 0, &mut scratch) !=
+                hash64_framed_with_scratch(&["a", "bc"], 0, &mut scratch));
+        assert_eq!(hash64_framed_with_scratch::<&[u8]>(&[], 123, &mut scratch),
+                   hash64_with_seed(b"", 123));
+    }
+
+    #[test]
+    fn test_hash64_iter_items() {
+        let parts = ["hello", "", "world", "!"];
+
+        assert_eq!(hash64_iter_items(parts.iter()), hash64_framed(&parts));
+        assert_eq!(hash64_iter_items((0..4_u8).map(|i| vec![i; i as usize])),
+                   hash64_framed(&[vec![], vec![1], vec![2, 2], vec![3, 3, 3]]));
+        assert_eq!(hash64_iter_items(Vec::<&[u8]>::new()), hash64(b""));
+        assert!(hash64_iter_items(vec!["ab", "c"]) != hash64_iter_items(vec!["a", "bc"]));
+    }
+
+    #[test]
+    fn test_hash_iter_ext() {
+        assert_eq!(["a", "b"].iter().farm_hash64(), hash64_framed(&["a", "b"]));
+        assert_eq!(["a", "b"].iter().cloned().farm_hash64(),
+                   hash64(b"\x01\0\0\0\0\0\0\0a\x01\0\0\0\0\0\0\0b"));
+        assert!(["a", "b"].iter().farm_hash64() != ["ab"].iter().farm_hash64());
+        assert!(["a", "b"].iter().farm_hash64() != hash64(b"ab"));
+    }
+
+    #[test]
+    fn test_hash64_row() {
+        let schema = [FieldType::UInt, FieldType::String, FieldType::Bytes];
+        let values: [&[u8]; 3] = [&[1, 0, 0, 0], b"hello", b""];
+        let h = hash64_row(&schema, &values);
+
+        assert_eq!(h, hash64_row(&schema, &values));
+        assert_eq!(h,
+                   hash64(b"\x03\x04\0\0\0\0\0\0\0\x01\0\0\0\
+                            \x05\x05\0\0\0\0\0\0\0hello\
+                            \x06\0\0\0\0\0\0\0\0"));
+        assert!(hash64_row(&[FieldType::Int, FieldType::String, FieldType::Bytes], &values) != h);
+        assert!(hash64_row(&schema, &[&[2, 0, 0, 0], b"hello", b""]) != h);
+        assert!(hash64_row(&[FieldType::Bytes, FieldType::Bytes], &[b"a", b"bc"]) !=
+                hash64_row(&[FieldType::Bytes, FieldType::Bytes], &[b"ab", b"c"]));
+        assert_eq!(hash64_row(&[], &[]), hash64(b""));
+    }
+
+    #[test]
+    #[should_panic(expected = "values length must match schema")]
+    fn test_hash64_row_length_mismatch() {
+        let _ = hash64_row(&[FieldType::Bytes], &[]);
+    }
+
+    #[test]
+    fn test_context_hasher() {
+        let tenant1 = ContextHasher::new(b"tenant-1");
+        let tenant2 = ContextHasher::new(b"tenant-2");
+
+        assert_eq!(tenant1.seed(), fingerprint64(b"tenant-1"));
+        assert_eq!(tenant1.hash64(b"hello"),
+                   hash64_with_seed(b"hello", fingerprint64(b"tenant-1")));
+        assert_eq!(tenant1.hash64(b"hello"),
+                   ContextHasher::new("tenant-1").hash64(b"hello"));
+        assert!(tenant1.hash64(b"hello") != tenant2.hash64(b"hello"));
+    }
+
+    #[test]
+    fn test_hash64_combine() {
+        assert!(hash64_combine2(b"ab", b"c") != hash64_combine2(b"a", b"bc"));
+        assert!(hash64_combine2(b"ab", b"") != hash64_combine2(b"", b"ab"));
+        assert!(hash64_combine3(b"a", b"b", b"c") != hash64_combine3(b"ab", b"", b"c"));
+        assert!(hash64_combine4(b"a", b"b", b"c", b"d") !=
+                hash64_combine4(b"a", b"b", b"cd", b""));
+        assert!(hash64_combine2(b"a", b"b") != hash64_combine3(b"a", b"b", b""));
+
+        assert_eq!(hash64_combine2(b"ab", b"c"),
+                   combine64(combine64(0, hash64_with_seed(b"ab", 2)),
+                             hash64_with_seed(b"c", 1)));
+        assert_eq!(hash64_combine3("a", vec![b'b'], b"c"), hash64_combine3(b"a", b"b", b"c"));
+    }
+
+    #[test]
+    fn test_hash_table() {
+        let entries = ["foo", "bar", "baz"];
+
+        let (hashes, collisions) = hash_table(&entries, 64);
+
+        assert_eq!(hashes,
+                   entries.iter().map(hash64).collect::<Vec<u64>>());
+        assert!(collisions.is_empty());
+
+        // three entries can't fit in one bit without a collision
+        let (hashes, collisions) = hash_table(&entries, 1);
+
+        assert!(hashes.iter().all(|&h| h < 2));
+        assert!(!collisions.is_empty());
+
+        for &(i, j) in &collisions {
+            assert!(i < j);
+            assert_eq!(hashes[i], hashes[j]);
+        }
+
+        let pairs = (0..3)
+            .flat_map(|i| (i + 1..3).map(move |j| (i, j)))
+            .filter(|&(i, j)| hashes[i] == hashes[j])
+            .collect::<Vec<(usize, usize)>>();
+
+        assert_eq!(collisions, pairs);
+
+        assert_eq!(hash_table(&[], 8), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_birthday_collision_probability() {
+        assert_eq!(birthday_collision_probability(0, 64), 0.0);
+        assert_eq!(birthday_collision_probability(1, 64), 0.0);
+        assert!((birthday_collision_probability(77163, 32) - 0.5).abs() < 1e-4);
+        assert!((birthday_collision_probability(1 << 32, 64) - 0.393469).abs() < 1e-6);
+        assert!((birthday_collision_probability(10_000_000, 64) - 2.710501e-6).abs() < 1e-11);
+        assert!(birthday_collision_probability(1 << 20, 16) > 0.999999);
+    }
+
+    #[test]
+    fn test_hash_128_to_64() {
+        let a = fingerprint64(b"hello word");
+        let b = fingerprint64(b"hello");
+
+        assert_eq!((a, b), (2862784602449412590, 13009744463427800296));
+        assert_eq!(hash_128_to_64(((a as u128) << 64) | b as u128), 18232254801699557664);
+        assert_eq!(hash_128_to_64(((b as u128) << 64) | a as u128), 220334285685584803);
+        assert_eq!(hash_128_to_64(123), 4011577241381678309);
+        assert_eq!(combine64(a, b), 18232254801699557664);
+    }
+
+    #[test]
+    fn test_combine() {
+        let a = fingerprint64(b"hello word");
+        let b = fingerprint64(b"hello");
+
+        assert_eq!(combine(&[]), 0);
+        assert_eq!(combine(&[a]), 4922524349633584399);
+        assert_eq!(combine(&[a, b]), 14514901023875078344);
+        assert_eq!(combine(&[b, a]), 16024007568661618165);
+        assert_eq!(combine(&[a, b]), combine64(combine64(2, a), b));
+        assert!(combine(&[0, a]) != combine(&[a]));
+
+        let h = fingerprint128(b"hello word");
+
+        assert_eq!(combine128(&[]), u64::MAX as u128);
+        assert_eq!(combine128(&[h, 123]),
+                   (2246147541646283439_u128 << 64) | 9347663775458683074);
+        assert_eq!(combine128(&[123, h]),
+                   (67747132441854726_u128 << 64) | 6497425885201704560);
+    }
+
+    #[test]
+    fn test_accumulator() {
+        assert_eq!(combine64(0, 123), 4011577241381678309);
+        assert!(combine64(1, 2) != combine64(2, 1));
+
+        let parts: [&[u8]; 3] = [b"hello", b"world", b""];
+
+        assert_eq!(parts.iter().fold(Accumulator::new(), |acc, part| acc.add(part)).finish(),
+                   parts.iter().fold(0, |acc, part| combine64(acc, hash64(part))));
+        assert_eq!(Accumulator::with_seed(123).add(b"hello").finish(),
+                   combine64(123, hash64(b"hello")));
+        assert_eq!(Accumulator::new().finish(), 0);
+    }
+
+    #[test]
+    fn test_hash_option_result() {
+        let none: Option<&[u8]> = None;
+
+        assert!(hash64_option(&Some(b"")) != hash64_option(&none));
+        assert!(hash128_option(&Some(b"")) != hash128_option(&none));
+        assert_eq!(hash64_option(&Some(b"hello")), hash64(b"\x01hello"));
+
+        let ok: Result<&[u8], &[u8]> = Ok(b"a");
+        let err: Result<&[u8], &[u8]> = Err(b"a");
+
+        assert!(hash64_result(&ok) != hash64_result(&err));
+        assert!(hash128_result(&ok) != hash128_result(&err));
+        assert_eq!(hash64_result(&ok), hash64(b"\x00a"));
+        assert_eq!(hash128_result(&err), hash128(b"\x01a"));
     }
 }
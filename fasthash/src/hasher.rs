@@ -1,51 +1,144 @@
-#[cfg(feature = "i128")]
-use std::mem;
+#[cfg(feature = "std")]
 use std::io;
+use std::cmp;
+use std::cell::Cell;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
 use std::cell::RefCell;
+#[cfg(feature = "std")]
 use std::marker::PhantomData;
-use std::hash::{Hasher, BuildHasher};
+use std::hash::Hasher;
+#[cfg(feature = "alloc")]
+use std::hash::BuildHasher;
 
-use rand::{Rand, Rng};
+#[cfg(feature = "std")]
+use rand::Rng;
+#[cfg(feature = "std")]
 use xoroshiro128::{SeedableRng, Xoroshiro128Rng};
 
-#[cfg(feature = "i128")]
-use extprim::i128::i128;
-use extprim::u128::u128;
-
 /// Generate a good, portable, forever-fixed hash value
 pub trait Fingerprint<T> {
     /// This is intended to be a good fingerprinting primitive.
+    #[must_use]
     fn fingerprint(&self) -> T;
 }
 
+/// Builds a 128-bit hash value from its `(high, low)` 64-bit halves.
+///
+/// This is the convention of every 128-bit value of the crate split in two words,
+/// e.g. `(hash1, hash2)` of `SpookyHash`, with the high half first.
+#[inline]
+#[must_use]
+pub fn u128_from_parts(high: u64, low: u64) -> u128 {
+    ((high as u128) << 64) | low as u128
+}
+
+/// Splits a 128-bit hash value into its `(high, low)` 64-bit halves, see `u128_from_parts`.
+#[inline]
+#[must_use]
+pub fn u128_to_parts(v: u128) -> (u64, u64) {
+    ((v >> 64) as u64, v as u64)
+}
+
+/// Builds a 128-bit hash value from a `[high, low]` array, in the order of `u128_from_parts`.
+#[inline]
+#[must_use]
+pub fn u128_from_array(v: [u64; 2]) -> u128 {
+    u128_from_parts(v[0], v[1])
+}
+
+/// Splits a 128-bit hash value into a `[high, low]` array, in the order of `u128_to_parts`.
+#[inline]
+#[must_use]
+pub fn u128_to_array(v: u128) -> [u64; 2] {
+    let (high, low) = u128_to_parts(v);
+
+    [high, low]
+}
+
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
-pub trait BuildHasherExt: BuildHasher {
+pub trait BuildFastHasher: BuildHasher {
     type FastHasher: FastHasher;
 }
 
+// Without `alloc` there are no hashers to build, so every `FastHash` qualifies.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+pub trait BuildFastHasher {}
+
+#[cfg(not(feature = "alloc"))]
+impl<T> BuildFastHasher for T {}
+
 /// Fast non-cryptographic hash functions
-pub trait FastHash: BuildHasherExt {
+///
+/// An algorithm only implements `hash_with_seed`, and `hash` uses the default seed.
+/// A seed-less algorithm implements it by ignoring the seed.
+/// Implementing `hash` alone doesn't compile:
+///
+/// ```compile_fail
+/// use fasthash::FastHash;
+///
+/// struct LenHash {}
+///
+/// impl FastHash for LenHash {
+///     type Value = u64;
+///     type Seed = ();
+///
+///     fn hash<T: AsRef<[u8]>>(bytes: T) -> u64 {
+///         bytes.as_ref().len() as u64
+///     }
+/// }
+/// ```
+pub trait FastHash: BuildFastHasher {
     /// The output hash generated value.
     type Value;
     /// The seed to generate hash value.
-    type Seed: Default + Copy + Rand;
+    type Seed: Default + Copy;
 
     /// Hash functions for a byte array.
     /// For convenience, a seed is also hashed into the result.
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: Self::Seed) -> Self::Value;
+    #[must_use]
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: Self::Seed) -> Self::Value;
 
     /// Hash functions for a byte array.
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> Self::Value {
+    #[inline]
+    #[must_use]
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> Self::Value {
         Self::hash_with_seed(bytes, Default::default())
     }
+
+    /// Hash functions for a byte array, with the default seed of the algorithm, e.g. zero.
+    ///
+    /// Generic code may seed every algorithm uniformly through it, whatever the width of
+    /// its seed. Unlike `hash`, which an algorithm may implement with an unseeded variant,
+    /// this always goes through `hash_with_seed`.
+    #[inline]
+    #[must_use]
+    fn hash_with_default_seed<T: AsRef<[u8]>>(bytes: T) -> Self::Value {
+        Self::hash_with_seed(bytes, Self::Seed::default())
+    }
 }
 
+/// Marker for the `FastHash` functions whose output is a forever-fixed fingerprint.
+///
+/// Unlike most hash functions, which may change from time to time or differ on
+/// different platforms, the values of a `StableHash` may be persisted to disk.
+pub trait StableHash: FastHash {}
+
 /// Fast non-cryptographic hasher
-pub trait FastHasher: Hasher
+#[cfg(feature = "alloc")]
+pub trait FastHasher: Hasher + Default
     where Self: Sized
 {
     /// The seed to generate hash value.
+    #[cfg(feature = "std")]
     type Seed: Default + Copy + From<Seed>;
+    /// The seed to generate hash value.
+    #[cfg(not(feature = "std"))]
+    type Seed: Default + Copy;
 
     /// Constructs a new `FastHasher`.
     #[inline]
@@ -54,15 +147,27 @@ pub trait FastHasher: Hasher
     }
 
     /// Constructs a new `FastHasher` with a random seed.
+    #[cfg(feature = "std")]
     fn new_with_random_seed() -> Self {
         Self::with_seed(Seed::gen().into())
     }
 
     /// Constructs a new `FastHasher` with seed.
     fn with_seed(seed: Self::Seed) -> Self;
+
+    /// Writes the length of the bytes, as a little-endian `u64`, and then the bytes.
+    ///
+    /// Unlike `write`, whose boundaries are invisible, the framed writes of
+    /// `("a", "bc")` and `("ab", "c")` produce different hashes.
+    #[inline]
+    fn write_framed(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
 }
 
 /// Hasher in the buffer mode for short key
+#[cfg(feature = "alloc")]
 pub trait BufHasher: FastHasher + AsRef<[u8]> {
     /// Constructs a buffered hasher with capacity and seed
     fn with_capacity_and_seed(capacity: usize, seed: Option<Self::Seed>) -> Self;
@@ -81,6 +186,7 @@ pub trait BufHasher: FastHasher + AsRef<[u8]> {
 }
 
 /// Hasher in the streaming mode without buffer
+#[cfg(feature = "std")]
 pub trait StreamHasher: FastHasher + Sized {
     /// Writes the stream into this hasher.
     fn write_stream<R: io::Read>(&mut self, r: &mut R) -> io::Result<usize> {
@@ -121,22 +227,84 @@ pub trait StreamHasher: FastHasher + Sized {
 }
 
 /// A trait which represents the ability to hash an arbitrary stream of bytes.
+#[cfg(feature = "alloc")]
 pub trait HasherExt: Hasher {
     /// Completes a round of hashing, producing the output hash generated.
+    #[must_use]
     fn finish_ext(&self) -> u128;
+}
+
+/// A `BuildHasher` whose hashers produce 128-bit values with `HasherExt`.
+///
+/// It is implemented for every `BuildHasher` building a `HasherExt`, e.g.
+/// `spooky::SpookyBuildHasher` or the `FarmHash128` and `SpookyHash128` hash functions,
+/// so a map keyed on 128-bit hashes may take any of them.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::hash::Hasher;
+///
+/// use fasthash::{BuildHasherExt, HasherExt};
+/// use fasthash::spooky::SpookyBuildHasher;
+///
+/// /// Content-addressed blobs, keyed on their full 128-bit hash.
+/// struct BlobStore<S> {
+///     build: S,
+///     blobs: HashMap<u128, Vec<u8>>,
+/// }
+///
+/// impl<S: BuildHasherExt> BlobStore<S> {
+///     fn insert(&mut self, blob: &[u8]) -> u128 {
+///         let mut h = self.build.build_hasher_ext();
+///
+///         h.write(blob);
+///
+///         let key = h.finish_ext();
+///
+///         self.blobs.insert(key, blob.to_vec());
+///         key
+///     }
+/// }
+///
+/// let mut store = BlobStore {
+///     build: SpookyBuildHasher::with_seed((123, 456)),
+///     blobs: HashMap::new(),
+/// };
+/// let key = store.insert(b"hello");
+///
+/// assert_eq!(key, store.build.hash_bytes_ext(b"hello"));
+/// assert_eq!(store.blobs[&key], b"hello");
+/// ```
+#[cfg(feature = "alloc")]
+pub trait BuildHasherExt: BuildHasher {
+    /// The type of the 128-bit hasher created.
+    type ExtHasher: HasherExt;
+
+    /// Creates a new 128-bit hasher.
+    fn build_hasher_ext(&self) -> Self::ExtHasher;
 
-    /// Writes a single `u128` into this hasher.
-    #[cfg(feature = "i128")]
+    /// Calculates the 128-bit hash of a byte array with a new hasher.
     #[inline]
-    fn write_u128(&mut self, i: u128) {
-        self.write(&unsafe { mem::transmute::<_, [u8; 16]>(i) })
+    #[must_use]
+    fn hash_bytes_ext(&self, bytes: &[u8]) -> u128 {
+        let mut h = self.build_hasher_ext();
+
+        h.write(bytes);
+        h.finish_ext()
     }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: BuildHasher> BuildHasherExt for B
+    where B::Hasher: HasherExt
+{
+    type ExtHasher = B::Hasher;
 
-    /// Writes a single `i128` into this hasher.
-    #[cfg(feature = "i128")]
     #[inline]
-    fn write_i128(&mut self, i: i128) {
-        self.write_u128(i.as_u128())
+    fn build_hasher_ext(&self) -> B::Hasher {
+        self.build_hasher()
     }
 }
 
@@ -163,9 +331,11 @@ pub trait HasherExt: Hasher {
 ///
 /// city::hash128_with_seed(b"hello world", Seed::gen().into());
 /// ```
+#[cfg(feature = "std")]
 #[derive(Clone, Copy, Debug)]
 pub struct Seed(Xoroshiro128Rng);
 
+#[cfg(feature = "std")]
 impl Seed {
     #[inline]
     fn new() -> Seed {
@@ -183,6 +353,7 @@ impl Seed {
 
 macro_rules! impl_from_seed {
     ($target:ty) => (
+        #[cfg(feature = "std")]
         impl From<Seed> for $target {
             #[inline]
             fn from(seed: Seed) -> $target {
@@ -196,10 +367,25 @@ macro_rules! impl_from_seed {
 
 impl_from_seed!(u32);
 impl_from_seed!(u64);
-impl_from_seed!(u128);
 impl_from_seed!((u64, u64));
 impl_from_seed!((u64, u64, u64, u64));
 
+#[cfg(feature = "std")]
+impl From<Seed> for () {
+    #[inline]
+    fn from(_: Seed) {}
+}
+
+#[cfg(feature = "std")]
+impl From<Seed> for u128 {
+    #[inline]
+    fn from(seed: Seed) -> u128 {
+        let (hi, lo): (u64, u64) = seed.into();
+
+        u128_from_parts(hi, lo)
+    }
+}
+
 /// `RandomState` provides the default state for `HashMap` or `HashSet` types.
 ///
 /// A particular instance `RandomState` will create the same instances of
@@ -222,11 +408,13 @@ impl_from_seed!((u64, u64, u64, u64));
 /// assert_eq!(map.insert(37, "c"), Some("b"));
 /// assert_eq!(map[&37], "c");
 /// ```
+#[cfg(feature = "std")]
 pub struct RandomState<T: FastHash> {
     seed: Seed,
     phantom: PhantomData<T>,
 }
 
+#[cfg(feature = "std")]
 impl<T: FastHash> RandomState<T> {
     /// Constructs a new `RandomState` that is initialized with random keys.
     #[inline]
@@ -238,6 +426,7 @@ impl<T: FastHash> RandomState<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: FastHash> BuildHasher for RandomState<T> {
     type Hasher = T::FastHasher;
 
@@ -247,6 +436,7 @@ impl<T: FastHash> BuildHasher for RandomState<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: FastHash> Default for RandomState<T> {
     #[inline]
     fn default() -> Self {
@@ -254,6 +444,380 @@ impl<T: FastHash> Default for RandomState<T> {
     }
 }
 
+/// The error of writing more bytes than a `BoundedInputHasher` allows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InputTooLarge {
+    /// The maximum number of bytes allowed.
+    pub limit: usize,
+    /// The total number of bytes the rejected write would have produced.
+    pub len: usize,
+}
+
+impl fmt::Display for InputTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "input of {} bytes exceeds the limit of {} bytes",
+               self.len,
+               self.limit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for InputTooLarge {}
+
+/// A `Hasher` wrapper enforcing a maximum input size.
+///
+/// `try_write` rejects a write that would exceed the limit with an `InputTooLarge` error,
+/// without passing it to the inner hasher.
+/// The plain `Hasher::write` panics in debug builds, and drops the write in release builds,
+/// where the error is reported by `check` and `try_finish`.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{BoundedInputHasher, FastHasher, FarmHasher};
+///
+/// let mut h = BoundedInputHasher::new(FarmHasher::new(), 8);
+///
+/// assert!(h.try_write(b"hello").is_ok());
+/// assert!(h.try_write(b"world").is_err());
+/// assert!(h.try_finish().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoundedInputHasher<H> {
+    inner: H,
+    limit: usize,
+    len: usize,
+    error: Option<InputTooLarge>,
+}
+
+impl<H: Hasher> BoundedInputHasher<H> {
+    /// Constructs a new `BoundedInputHasher` accepting at most `limit` bytes.
+    #[inline]
+    pub fn new(inner: H, limit: usize) -> Self {
+        BoundedInputHasher {
+            inner: inner,
+            limit: limit,
+            len: 0,
+            error: None,
+        }
+    }
+
+    /// Returns the maximum number of bytes allowed.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes some data into the inner hasher, unless it would exceed the limit.
+    pub fn try_write(&mut self, bytes: &[u8]) -> Result<(), InputTooLarge> {
+        match self.len.checked_add(bytes.len()) {
+            Some(len) if len <= self.limit => {
+                self.inner.write(bytes);
+                self.len = len;
+
+                Ok(())
+            }
+            len => {
+                let err = InputTooLarge {
+                    limit: self.limit,
+                    len: len.unwrap_or(usize::MAX),
+                };
+
+                self.error = Some(err);
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the first error of the writes, if any.
+    #[inline]
+    pub fn check(&self) -> Result<(), InputTooLarge> {
+        self.error.map_or(Ok(()), Err)
+    }
+
+    /// Completes a round of hashing, unless a write exceeded the limit.
+    #[inline]
+    pub fn try_finish(&self) -> Result<u64, InputTooLarge> {
+        self.check().map(|_| self.inner.finish())
+    }
+
+    /// Unwraps this `BoundedInputHasher`, returning the inner hasher.
+    #[inline]
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: Hasher> Hasher for BoundedInputHasher<H> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        if let Err(err) = self.try_write(bytes) {
+            if cfg!(debug_assertions) {
+                panic!("{}", err);
+            }
+        }
+    }
+}
+
+/// A `Hasher` wrapper writing a type tag before every value, for schema-aware content hashes.
+///
+/// Each `write_*` method prepends a distinct tag byte, and variable-sized values are also
+/// framed by their length as a little-endian `u64`, so an integer field and a string field
+/// holding the same bytes never collide.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::{FastHasher, FarmHasher, TypedHasher};
+///
+/// let mut h1 = TypedHasher::new(FarmHasher::new());
+/// let mut h2 = TypedHasher::new(FarmHasher::new());
+///
+/// h1.write_i64(1);
+/// h2.write_bytes(&1i64.to_le_bytes());
+///
+/// assert!(h1.finish() != h2.finish());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TypedHasher<H> {
+    inner: H,
+}
+
+impl<H: Hasher> TypedHasher<H> {
+    const TAG_I64: u8 = 1;
+    const TAG_STR: u8 = 2;
+    const TAG_BYTES: u8 = 3;
+
+    /// Constructs a new `TypedHasher` over the inner hasher.
+    #[inline]
+    pub fn new(inner: H) -> Self {
+        TypedHasher { inner: inner }
+    }
+
+    /// Writes a tagged `i64` value.
+    #[inline]
+    pub fn write_i64(&mut self, v: i64) {
+        self.inner.write_u8(Self::TAG_I64);
+        self.inner.write(&v.to_le_bytes());
+    }
+
+    /// Writes a tagged and length framed string value.
+    #[inline]
+    pub fn write_str(&mut self, v: &str) {
+        self.write_tagged(Self::TAG_STR, v.as_bytes());
+    }
+
+    /// Writes a tagged and length framed byte array value.
+    #[inline]
+    pub fn write_bytes(&mut self, v: &[u8]) {
+        self.write_tagged(Self::TAG_BYTES, v);
+    }
+
+    /// Completes a round of hashing, producing the output hash generated.
+    #[inline]
+    #[must_use]
+    pub fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    /// Unwraps this `TypedHasher`, returning the inner hasher.
+    #[inline]
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+
+    fn write_tagged(&mut self, tag: u8, v: &[u8]) {
+        self.inner.write_u8(tag);
+        self.inner.write(&(v.len() as u64).to_le_bytes());
+        self.inner.write(v);
+    }
+}
+
+/// A `Hasher` wrapper emitting the running digest at every `chunk_size` bytes boundary,
+/// for streaming attestation protocols.
+///
+/// The running digest is peeked with `Hasher::finish`, which doesn't consume the inner
+/// hasher, so the writes keep accumulating toward the digest of the whole stream.
+/// The callback receives the number of bytes written so far and the digest of them.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{farm, ChunkedDigestHasher, FastHasher, FarmHasher};
+///
+/// let mut digests = Vec::new();
+///
+/// {
+///     let mut h = ChunkedDigestHasher::new(FarmHasher::new(), 4, |len, digest| {
+///         digests.push((len, digest))
+///     });
+///
+///     h.write(b"hello world");
+///
+///     assert_eq!(h.finish(), farm::hash64(b"hello world"));
+/// }
+///
+/// assert_eq!(digests, [(4, farm::hash64(b"hell")), (8, farm::hash64(b"hello wo"))]);
+/// ```
+#[derive(Clone)]
+pub struct ChunkedDigestHasher<H, F> {
+    inner: H,
+    chunk_size: usize,
+    pos: usize,
+    len: usize,
+    callback: F,
+}
+
+impl<H: Hasher, F: FnMut(usize, u64)> ChunkedDigestHasher<H, F> {
+    /// Constructs a new `ChunkedDigestHasher` calling `callback` every `chunk_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[inline]
+    pub fn new(inner: H, chunk_size: usize, callback: F) -> Self {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+
+        ChunkedDigestHasher {
+            inner: inner,
+            chunk_size: chunk_size,
+            pos: 0,
+            len: 0,
+            callback: callback,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Unwraps this `ChunkedDigestHasher`, returning the inner hasher.
+    #[inline]
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: Hasher, F: FnMut(usize, u64)> Hasher for ChunkedDigestHasher<H, F> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    fn write(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let n = cmp::min(self.chunk_size - self.pos, bytes.len());
+
+            self.inner.write(&bytes[..n]);
+            self.pos += n;
+            self.len += n;
+            bytes = &bytes[n..];
+
+            if self.pos == self.chunk_size {
+                self.pos = 0;
+
+                (self.callback)(self.len, self.inner.finish());
+            }
+        }
+    }
+}
+
+/// A `Hasher` wrapper enforcing single-use semantics.
+///
+/// In debug builds `OneShotHasher` panics if `finish` is called more than once or
+/// if `write` follows `finish`, catching hashers that are accidentally reused
+/// without a reset. Release builds don't check and simply forward to the inner hasher.
+///
+/// # Example
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// use fasthash::{farm, FastHasher, FarmHasher, OneShotHasher};
+///
+/// let mut h = OneShotHasher::new(FarmHasher::new());
+///
+/// h.write(b"hello");
+/// h.write(b"world");
+///
+/// assert_eq!(h.finish(), farm::hash64(b"helloworld"));
+/// ```
+#[derive(Clone)]
+pub struct OneShotHasher<H> {
+    inner: H,
+    finished: Cell<bool>,
+}
+
+impl<H: Hasher> OneShotHasher<H> {
+    /// Constructs a new `OneShotHasher` wrapping the hasher.
+    #[inline]
+    pub fn new(inner: H) -> Self {
+        OneShotHasher {
+            inner: inner,
+            finished: Cell::new(false),
+        }
+    }
+
+    /// Returns `true` if `finish` was already called.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished.get()
+    }
+
+    /// Unwraps this `OneShotHasher`, returning the inner hasher.
+    #[inline]
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: Hasher> Hasher for OneShotHasher<H> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        debug_assert!(!self.finished.get(), "hasher finished more than once");
+
+        self.finished.set(true);
+        self.inner.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        debug_assert!(!self.finished.get(), "hasher written after finish");
+
+        self.inner.write(bytes)
+    }
+}
+
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 macro_rules! impl_fasthash {
     ($hasher:ident, $hash:ident) => (
@@ -266,12 +830,13 @@ macro_rules! impl_fasthash {
             }
         }
 
-        impl $crate::hasher::BuildHasherExt for $hash {
+        impl $crate::hasher::BuildFastHasher for $hash {
             type FastHasher = $hasher;
         }
     )
 }
 
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_hasher {
@@ -280,7 +845,22 @@ macro_rules! impl_hasher {
         #[derive(Clone)]
         pub struct $hasher {
             seed: Option<<$hash as $crate::hasher::FastHash>::Seed>,
-            bytes: Vec<u8>,
+            bytes: $crate::alloc::vec::Vec<u8>,
+        }
+
+        impl $hasher {
+            /// Constructs a new hasher whose buffer holds `capacity` bytes without reallocating,
+            /// for when the total size of the written bytes is known ahead of time.
+            #[inline]
+            pub fn with_capacity(capacity: usize) -> Self {
+                <Self as $crate::hasher::BufHasher>::with_capacity_and_seed(capacity, None)
+            }
+
+            /// Returns the number of bytes the buffer can hold without reallocating.
+            #[inline]
+            pub fn capacity(&self) -> usize {
+                self.bytes.capacity()
+            }
         }
 
         impl Default for $hasher {
@@ -300,6 +880,26 @@ macro_rules! impl_hasher {
             fn write(&mut self, bytes: &[u8]) {
                 self.bytes.extend_from_slice(bytes)
             }
+            #[inline]
+            fn write_u8(&mut self, i: u8) {
+                self.bytes.push(i)
+            }
+            #[inline]
+            fn write_u16(&mut self, i: u16) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
+            #[inline]
+            fn write_u32(&mut self, i: u32) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
+            #[inline]
+            fn write_u64(&mut self, i: u64) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
+            #[inline]
+            fn write_usize(&mut self, i: usize) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
         }
 
         impl $crate::hasher::FastHasher for $hasher {
@@ -323,13 +923,29 @@ macro_rules! impl_hasher {
             }
         }
 
+        impl ::std::iter::Extend<u8> for $hasher {
+            #[inline]
+            fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+                self.bytes.extend(iter)
+            }
+        }
+
+        impl<'a> ::std::iter::Extend<&'a [u8]> for $hasher {
+            #[inline]
+            fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+                for bytes in iter {
+                    self.bytes.extend_from_slice(bytes)
+                }
+            }
+        }
+
         impl $crate::hasher::BufHasher for $hasher {
             #[inline]
             fn with_capacity_and_seed(capacity: usize, seed: Option<Self::Seed>) -> Self
             {
                 $hasher {
                     seed: seed,
-                    bytes: Vec::with_capacity(capacity),
+                    bytes: $crate::alloc::vec::Vec::with_capacity(capacity),
                 }
             }
         }
@@ -338,6 +954,7 @@ macro_rules! impl_hasher {
     )
 }
 
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_hasher_ext {
@@ -346,7 +963,7 @@ macro_rules! impl_hasher_ext {
         #[derive(Clone)]
         pub struct $hasher {
             seed: Option<<$hash as $crate::hasher::FastHash>::Seed>,
-            bytes: Vec<u8>,
+            bytes: $crate::alloc::vec::Vec<u8>,
         }
 
         impl $hasher {
@@ -356,6 +973,19 @@ macro_rules! impl_hasher_ext {
                     || $hash::hash(&self.bytes),
                     |seed| $hash::hash_with_seed(&self.bytes, seed))
             }
+
+            /// Constructs a new hasher whose buffer holds `capacity` bytes without reallocating,
+            /// for when the total size of the written bytes is known ahead of time.
+            #[inline]
+            pub fn with_capacity(capacity: usize) -> Self {
+                <Self as $crate::hasher::BufHasher>::with_capacity_and_seed(capacity, None)
+            }
+
+            /// Returns the number of bytes the buffer can hold without reallocating.
+            #[inline]
+            pub fn capacity(&self) -> usize {
+                self.bytes.capacity()
+            }
         }
 
         impl Default for $hasher {
@@ -367,12 +997,32 @@ macro_rules! impl_hasher_ext {
         impl ::std::hash::Hasher for $hasher {
             #[inline]
             fn finish(&self) -> u64 {
-                self.finalize().low64()
+                self.finalize() as u64
             }
             #[inline]
             fn write(&mut self, bytes: &[u8]) {
                 self.bytes.extend_from_slice(bytes)
             }
+            #[inline]
+            fn write_u8(&mut self, i: u8) {
+                self.bytes.push(i)
+            }
+            #[inline]
+            fn write_u16(&mut self, i: u16) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
+            #[inline]
+            fn write_u32(&mut self, i: u32) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
+            #[inline]
+            fn write_u64(&mut self, i: u64) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
+            #[inline]
+            fn write_usize(&mut self, i: usize) {
+                self.bytes.extend_from_slice(&i.to_ne_bytes())
+            }
         }
 
         impl $crate::hasher::HasherExt for $hasher {
@@ -403,13 +1053,29 @@ macro_rules! impl_hasher_ext {
             }
         }
 
+        impl ::std::iter::Extend<u8> for $hasher {
+            #[inline]
+            fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+                self.bytes.extend(iter)
+            }
+        }
+
+        impl<'a> ::std::iter::Extend<&'a [u8]> for $hasher {
+            #[inline]
+            fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+                for bytes in iter {
+                    self.bytes.extend_from_slice(bytes)
+                }
+            }
+        }
+
         impl $crate::hasher::BufHasher for $hasher {
             #[inline]
             fn with_capacity_and_seed(capacity: usize, seed: Option<Self::Seed>) -> Self
             {
                 $hasher {
                     seed: seed,
-                    bytes: Vec::with_capacity(capacity),
+                    bytes: $crate::alloc::vec::Vec::with_capacity(capacity),
                 }
             }
         }
@@ -418,18 +1084,36 @@ macro_rules! impl_hasher_ext {
     )
 }
 
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+macro_rules! impl_fasthash {
+    ($hasher:ident, $hash:ident) => ()
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_hasher {
+    ($hasher:ident, $hash:ident) => ()
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_hasher_ext {
+    ($hasher:ident, $hash:ident) => ()
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::Into;
     use std::collections::HashMap;
 
-    use extprim::u128::u128;
-
     use city::{CityHash32, CityHash64, CityHash128};
     #[cfg(feature = "sse42")]
     use city::CityHashCrc128;
 
-    use farm::{FarmHash32, FarmHash64, FarmHash128};
+    use farm::{FarmHash32, FarmHash64, FarmHash128, FarmHasher64, FarmHasher128};
     use lookup3::Lookup3;
 
     use metro::{MetroHash64_1, MetroHash64_2, MetroHash128_1, MetroHash128_2};
@@ -442,7 +1126,7 @@ mod tests {
                   Murmur2_x86_64};
     use murmur3::{Murmur3_x86_32, Murmur3_x86_128, Murmur3_x64_128};
     use sea::SeaHash;
-    use spooky::{SpookyHash32, SpookyHash64, SpookyHash128};
+    use spooky::{SpookyHash32, SpookyHash64, SpookyHash128, SpookyHasher128, SpookyBuildHasher};
 
     use t1ha::{T1ha64Le, T1ha64Be, T1ha32Le, T1ha32Be};
     #[cfg(feature = "sse42")]
@@ -460,9 +1144,9 @@ mod tests {
 
         assert!(u0 != 0);
         assert!(u1 != 0);
-        assert!(u2 != u128::zero());
+        assert!(u2 != 0);
         assert_eq!(u0, u1 as u32);
-        assert_eq!(u1, u2.high64());
+        assert_eq!(u1, (u2 >> 64) as u64);
 
         s = Seed::gen();
 
@@ -474,10 +1158,10 @@ mod tests {
 
         assert!(u0 != 0);
         assert!(u1 != 0);
-        assert!(u2 != u128::zero());
+        assert!(u2 != 0);
         assert!(u0 as u64!= u1);
-        assert!(u1 != u2.low64());
-        assert!(u1 != u2.high64());
+        assert!(u1 != u2 as u64);
+        assert!(u1 != (u2 >> 64) as u64);
 
         u0 = Seed::gen().into();
         u1 = Seed::gen().into();
@@ -485,10 +1169,249 @@ mod tests {
 
         assert!(u0 != 0);
         assert!(u1 != 0);
-        assert!(u2 != u128::zero());
+        assert!(u2 != 0);
         assert!(u0 as u64!= u1);
-        assert!(u1 != u2.low64());
-        assert!(u1 != u2.high64());
+        assert!(u1 != u2 as u64);
+        assert!(u1 != (u2 >> 64) as u64);
+    }
+
+    #[test]
+    fn test_bounded_input_hasher() {
+        let mut h = BoundedInputHasher::new(FarmHasher64::new(), 10);
+
+        assert_eq!(h.try_write(b"hello"), Ok(()));
+        assert_eq!(h.try_write(b"world"), Ok(()));
+        assert_eq!(h.len(), 10);
+        assert_eq!(h.try_finish(), Ok(FarmHash64::hash(b"helloworld")));
+
+        assert_eq!(h.try_write(b"!"),
+                   Err(InputTooLarge {
+                       limit: 10,
+                       len: 11,
+                   }));
+        assert_eq!(h.len(), 10);
+        assert_eq!(h.finish(), FarmHash64::hash(b"helloworld"));
+        assert!(h.check().is_err());
+        assert!(h.try_finish().is_err());
+
+        let mut h = BoundedInputHasher::new(FarmHasher64::new(), 5);
+
+        h.write(b"hello");
+        assert_eq!(h.try_finish(), Ok(FarmHash64::hash(b"hello")));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "exceeds the limit")]
+    fn test_bounded_input_hasher_overflow() {
+        let mut h = BoundedInputHasher::new(FarmHasher64::new(), 4);
+
+        h.write(b"hello");
+    }
+
+    #[test]
+    fn test_typed_hasher() {
+        fn typed<F: FnOnce(&mut TypedHasher<FarmHasher64>)>(f: F) -> u64 {
+            let mut h = TypedHasher::new(FarmHasher64::new());
+
+            f(&mut h);
+
+            h.finish()
+        }
+
+        assert_eq!(typed(|h| h.write_i64(1)),
+                   FarmHash64::hash(b"\x01\x01\x00\x00\x00\x00\x00\x00\x00"));
+        assert_eq!(typed(|h| h.write_str("hello")), typed(|h| h.write_str("hello")));
+
+        assert!(typed(|h| h.write_i64(1)) != typed(|h| h.write_str("\x01\0\0\0\0\0\0\0")));
+        assert!(typed(|h| h.write_i64(1)) !=
+                typed(|h| h.write_bytes(b"\x01\0\0\0\0\0\0\0")));
+        assert!(typed(|h| h.write_str("hello")) != typed(|h| h.write_bytes(b"hello")));
+        assert!(typed(|h| {
+                    h.write_str("ab");
+                    h.write_str("c");
+                }) !=
+                typed(|h| {
+                    h.write_str("a");
+                    h.write_str("bc");
+                }));
+    }
+
+    #[test]
+    fn test_hasher_write_integers() {
+        let mut h = FarmHasher64::new();
+
+        h.write_u8(1);
+        h.write_u16(2);
+        h.write_u32(3);
+        h.write_u64(4);
+        h.write_usize(5);
+
+        let mut bytes = vec![1];
+
+        bytes.extend_from_slice(&2_u16.to_ne_bytes());
+        bytes.extend_from_slice(&3_u32.to_ne_bytes());
+        bytes.extend_from_slice(&4_u64.to_ne_bytes());
+        bytes.extend_from_slice(&5_usize.to_ne_bytes());
+
+        assert_eq!(h.as_slice(), &bytes[..]);
+        assert_eq!(h.finish(), FarmHash64::hash(&bytes));
+
+        let mut h = FarmHasher128::new();
+
+        h.write_u64(4);
+        assert_eq!(h.finish_ext(), FarmHash128::hash(4_u64.to_ne_bytes()));
+    }
+
+    #[test]
+    fn test_chunked_digest_hasher() {
+        let data = (0..10 * 1024).map(|i| (i * 7) as u8).collect::<Vec<u8>>();
+        let mut digests = Vec::new();
+
+        let digest = {
+            let mut h = ChunkedDigestHasher::new(FarmHasher64::new(), 1024, |len, digest| {
+                digests.push((len, digest))
+            });
+
+            for part in data.chunks(300) {
+                h.write(part);
+            }
+
+            assert_eq!(h.len(), data.len());
+
+            h.finish()
+        };
+
+        assert_eq!(digest, FarmHash64::hash(&data));
+        assert_eq!(digests.len(), 10);
+
+        for (i, &(len, digest)) in digests.iter().enumerate() {
+            assert_eq!(len, (i + 1) * 1024);
+            assert_eq!(digest, FarmHash64::hash(&data[..len]));
+        }
+    }
+
+    #[test]
+    fn test_one_shot_hasher() {
+        let mut h = OneShotHasher::new(FarmHasher64::new());
+
+        h.write(b"hello");
+        assert!(!h.is_finished());
+        assert_eq!(h.finish(), FarmHash64::hash(b"hello"));
+        assert!(h.is_finished());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "hasher finished more than once")]
+    fn test_one_shot_hasher_double_finish() {
+        let mut h = OneShotHasher::new(FarmHasher64::new());
+
+        h.write(b"hello");
+        let _ = h.finish();
+        let _ = h.finish();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "hasher written after finish")]
+    fn test_one_shot_hasher_write_after_finish() {
+        let mut h = OneShotHasher::new(FarmHasher64::new());
+
+        let _ = h.finish();
+        h.write(b"hello");
+    }
+
+    #[test]
+    fn test_build_hasher_ext() {
+        fn hash_parts<S: BuildHasherExt>(build: &S) -> u128 {
+            let mut h = build.build_hasher_ext();
+
+            h.write(b"hello");
+            h.write(b"world");
+            h.finish_ext()
+        }
+
+        assert_eq!(hash_parts(&FarmHash128::default()),
+                   FarmHash128::hash(b"helloworld"));
+        assert_eq!(hash_parts(&SpookyHash128::default()),
+                   SpookyHash128::hash(b"helloworld"));
+
+        let spooky_build = SpookyBuildHasher::with_seed((123, 456));
+        let mut h = SpookyHasher128::with_seed((123, 456));
+
+        h.write(b"helloworld");
+        assert_eq!(hash_parts(&spooky_build), h.finish_ext());
+        assert_eq!(spooky_build.hash_bytes_ext(b"helloworld"), h.finish_ext());
+        assert!(h.finish_ext() > u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_hash_with_default_seed() {
+        fn index<H: FastHash>(keys: &[&[u8]]) -> Vec<H::Value> {
+            keys.iter().map(H::hash_with_default_seed).collect()
+        }
+
+        let keys: [&[u8]; 3] = [b"hello", b"world", b""];
+
+        assert_eq!(index::<FarmHash64>(&keys),
+                   keys.iter().map(|k| ::farm::hash64_with_seed(k, 0)).collect::<Vec<_>>());
+        assert_eq!(index::<SpookyHash64>(&keys),
+                   keys.iter().map(|k| ::spooky::hash64_with_seed(k, 0)).collect::<Vec<_>>());
+        assert_eq!(index::<SpookyHash64>(&keys)[0], 6105954949053820864);
+    }
+
+    #[test]
+    fn test_u128_parts() {
+        let v = (123_u128 << 64) | 456;
+
+        assert_eq!(u128_to_parts(v), (123, 456));
+        assert_eq!(u128_from_parts(123, 456), v);
+        assert_eq!(u128_to_array(v), [123, 456]);
+        assert_eq!(u128_from_array([123, 456]), v);
+
+        for &v in &[0, 1, u64::MAX as u128, u128::MAX, SpookyHash128::hash(b"hello")] {
+            let (high, low) = u128_to_parts(v);
+
+            assert_eq!(u128_from_parts(high, low), v);
+            assert_eq!(u128_from_array(u128_to_array(v)), v);
+            assert_eq!(u128_to_array(v), [high, low]);
+        }
+
+        assert_eq!(u128_to_parts(SpookyHash128::hash(b"hello")),
+                   ::spooky::hash128_parts(b"hello", 0));
+    }
+
+    #[test]
+    fn test_seedless_fast_hash() {
+        pub struct LenHash {}
+
+        impl FastHash for LenHash {
+            type Value = u64;
+            type Seed = u64;
+
+            fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, _seed: u64) -> u64 {
+                bytes.as_ref().len() as u64
+            }
+        }
+
+        impl_hasher!(LenHasher, LenHash);
+
+        assert_eq!(LenHash::hash(b"hello"), 5);
+        assert_eq!(LenHash::hash_with_seed(b"hello", 123), 5);
+
+        let mut h = LenHasher::with_seed(123);
+
+        h.write(b"hello");
+        h.write(b"world");
+        assert_eq!(h.finish(), 10);
+
+        let mut h = LenHasher::with_capacity(16);
+
+        assert!(h.capacity() >= 16);
+
+        h.write(b"hello");
+        assert_eq!(h.finish(), 5);
     }
 
     macro_rules! test_hashmap_with_fixed_state {
@@ -0,0 +1,87 @@
+//! Snowflake-like 64-bit ids.
+//!
+//! Distributed id generators usually combine a node id, a timestamp and a per-node
+//! sequence number. `SnowflakeLikeHasher` hashes such a tuple into a well-distributed
+//! 64-bit id, rather than packing the fields into fixed bit ranges.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::id::SnowflakeLikeHasher;
+//!
+//! let ids = SnowflakeLikeHasher::new(7);
+//!
+//! assert_eq!(ids.id(1_500_000_000_000, 0), ids.id(1_500_000_000_000, 0));
+//! assert!(ids.id(1_500_000_000_000, 0) != ids.id(1_500_000_000_000, 1));
+//! ```
+//!
+use farm;
+
+/// Hashes `(node_id, timestamp_ms, seq)` tuples into 64-bit ids with `farm::hash64_framed`.
+///
+/// Every field is written as a full little-endian `u64`, so the timestamp never rolls over:
+/// any millisecond timestamp up to `u64::MAX` (about 584 million years after the epoch)
+/// is hashed as is, and no field can overflow into its neighbour.
+/// Ids are well distributed but not guaranteed unique; with 64-bit output a collision
+/// becomes likely only after about 2^32 ids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SnowflakeLikeHasher {
+    node_id: u64,
+}
+
+impl SnowflakeLikeHasher {
+    /// Constructs a new `SnowflakeLikeHasher` for the given node.
+    #[inline]
+    pub fn new(node_id: u64) -> Self {
+        SnowflakeLikeHasher { node_id: node_id }
+    }
+
+    /// Returns the node id.
+    #[inline]
+    pub fn node_id(&self) -> u64 {
+        self.node_id
+    }
+
+    /// Returns the id of the `seq`-th event of this node at `timestamp_ms`.
+    #[inline]
+    pub fn id(&self, timestamp_ms: u64, seq: u64) -> u64 {
+        farm::hash64_framed(&[self.node_id.to_le_bytes(),
+                              timestamp_ms.to_le_bytes(),
+                              seq.to_le_bytes()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use farm;
+    use super::*;
+
+    #[test]
+    fn test_snowflake_like_hasher() {
+        let ids = SnowflakeLikeHasher::new(7);
+
+        assert_eq!(ids.node_id(), 7);
+        assert_eq!(ids.id(123, 456), ids.id(123, 456));
+        assert_eq!(ids.id(123, 456),
+                   farm::hash64_framed(&[7u64.to_le_bytes(),
+                                         123u64.to_le_bytes(),
+                                         456u64.to_le_bytes()]));
+        assert!(ids.id(123, 456) != ids.id(456, 123));
+        assert!(ids.id(123, 456) != SnowflakeLikeHasher::new(8).id(123, 456));
+        assert!(ids.id(u64::MAX, 0) != ids.id(0, 0));
+
+        let mut seen = HashSet::new();
+
+        for node in 0..4 {
+            let ids = SnowflakeLikeHasher::new(node);
+
+            for ts in 0..16 {
+                for seq in 0..16 {
+                    assert!(seen.insert(ids.id(ts, seq)));
+                }
+            }
+        }
+    }
+}
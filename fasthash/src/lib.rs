@@ -53,60 +53,116 @@
 //! assert_eq!(map.insert(37, "c"), Some("b"));
 //! assert_eq!(map[&37], "c");
 //! ```
+//!
+//! # `no_std`
+//!
+//! Disable the default `std` feature to build on `core` only,
+//! where the one-shot `hashNN` and `fingerprintNN` functions and the `FastHash` trait
+//! are still available.
+//! The `alloc` feature brings back the `FastHasher` and `HasherExt` hashers,
+//! while the random seeds, `RandomState` and `StreamHasher` require `std`.
+//!
+//! ```toml
+//! [dependencies]
+//! fasthash = { version = "0.2", default-features = false, features = ["alloc"] }
+//! ```
 #![cfg_attr(feature="clippy", feature(plugin))]
 #![cfg_attr(feature="clippy", plugin(clippy))]
 
 #![warn(missing_docs)]
 
-extern crate extprim;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+#[cfg(feature = "std")]
 extern crate rand;
+#[cfg(feature = "std")]
 extern crate xoroshiro128;
 extern crate seahash;
 extern crate fasthash_sys as ffi;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "digest")]
+extern crate digest;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
 #[macro_use]
 mod hasher;
+pub mod cdc;
 pub mod city;
+pub mod dispatch;
 pub mod farm;
+#[cfg(feature = "alloc")]
+pub mod id;
 pub mod lookup3;
 pub mod metro;
+#[cfg(feature = "alloc")]
+pub mod minhash;
 pub mod mum;
 pub mod murmur;
 pub mod murmur2;
 pub mod murmur3;
+#[cfg(feature = "std")]
+pub mod registry;
+pub mod rsync;
 pub mod sea;
 pub mod spooky;
 pub mod t1ha;
+#[cfg(feature = "std")]
+pub mod testing;
 pub mod xx;
 
-pub use hasher::{Fingerprint, FastHash, FastHasher, BufHasher, StreamHasher, HasherExt, Seed,
-                 RandomState};
+pub use hasher::{Fingerprint, FastHash, StableHash, BoundedInputHasher, InputTooLarge,
+                 TypedHasher, ChunkedDigestHasher, OneShotHasher};
+pub use hasher::{u128_from_parts, u128_to_parts, u128_from_array, u128_to_array};
+#[cfg(feature = "alloc")]
+pub use hasher::{FastHasher, BufHasher, HasherExt, BuildHasherExt};
+#[cfg(feature = "std")]
+pub use hasher::{StreamHasher, Seed, RandomState};
 
-#[cfg(not(feature = "sse42"))]
+#[cfg(all(feature = "alloc", not(feature = "sse42")))]
 pub use city::{CityHasher64 as CityHasher, CityHasher128 as CityHasherExt};
-#[cfg(feature = "sse42")]
+#[cfg(all(feature = "alloc", feature = "sse42"))]
 pub use city::{CityHasher64 as CityHasher, CityHasherCrc128 as CityHasherExt};
 
+#[cfg(feature = "alloc")]
 pub use farm::{FarmHasher64 as FarmHasher, FarmHasher128 as FarmHasherExt};
+#[cfg(feature = "alloc")]
 pub use lookup3::Lookup3Hasher;
 
-#[cfg(not(feature = "sse42"))]
+#[cfg(all(feature = "alloc", not(feature = "sse42")))]
 pub use metro::{MetroHasher64_1 as MetroHasher, MetroHasher128_1 as MetroHasherExt};
-#[cfg(feature = "sse42")]
+#[cfg(all(feature = "alloc", feature = "sse42"))]
 pub use metro::{MetroHasher64Crc_1 as MetroHasher, MetroHasher128Crc_1 as MetroHasherExt};
 
+#[cfg(feature = "alloc")]
 pub use mum::MumHasher;
+#[cfg(feature = "alloc")]
 pub use murmur::MurmurHasher;
+#[cfg(feature = "alloc")]
 pub use murmur2::Murmur2Hasher_x64_64 as Murmur2Hasher;
+#[cfg(feature = "alloc")]
 pub use murmur3::{Murmur3Hasher_x64_128 as Murmur3Hasher,
                   Murmur3Hasher_x64_128 as Murmur3HasherExt};
+#[cfg(feature = "alloc")]
 #[doc(no_inline)]
 pub use sea::SeaHasher64 as SeaHasher;
+#[cfg(feature = "alloc")]
 pub use spooky::{SpookyHasher128 as SpookyHasher, SpookyHasher128 as SpookyHasherExt};
 
-#[cfg(not(feature = "sse42"))]
+#[cfg(all(feature = "alloc", not(feature = "sse42")))]
 pub use t1ha::T1ha64LeHasher as T1haHasher;
-#[cfg(feature = "sse42")]
+#[cfg(all(feature = "alloc", feature = "sse42"))]
 pub use t1ha::T1ha64CrcHasher as T1haHasher;
 
+#[cfg(feature = "alloc")]
 pub use xx::XXHasher64 as XXHasher;
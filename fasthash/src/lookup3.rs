@@ -23,11 +23,13 @@
 //! assert_eq!(h, hash(&"hello world") as u32);
 //! ```
 //!
-use std::os::raw::c_void;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `Lookup3` 32-bit hash functions
 pub struct Lookup3 {}
@@ -37,7 +39,7 @@ impl FastHash for Lookup3 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::lookup3(bytes.as_ref().as_ptr() as *const c_void,
                          bytes.as_ref().len() as i32,
@@ -50,14 +52,14 @@ impl_hasher!(Lookup3Hasher, Lookup3);
 
 /// `Lookup3` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     Lookup3::hash(v)
 }
 
 /// `Lookup3` 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Lookup3::hash_with_seed(v, seed)
 }
 
@@ -42,11 +42,11 @@
 #![allow(non_camel_case_types)]
 use std::mem;
 
-use extprim::u128::u128;
-
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `MetroHash` 64-bit hash functions
 pub struct MetroHash64_1 {}
@@ -56,7 +56,7 @@ impl FastHash for MetroHash64_1 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u64 {
         let mut hash = 0_u64;
 
         unsafe {
@@ -80,7 +80,7 @@ impl FastHash for MetroHash64_2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u64 {
         let mut hash = 0_u64;
 
         unsafe {
@@ -104,8 +104,8 @@ impl FastHash for MetroHash128_1 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u128 {
-        let mut hash = u128::zero();
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
+        let mut hash = 0_u128;
 
         unsafe {
             ffi::metrohash128_1(bytes.as_ref().as_ptr() as *const u8,
@@ -128,8 +128,8 @@ impl FastHash for MetroHash128_2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u128 {
-        let mut hash = u128::zero();
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
+        let mut hash = 0_u128;
 
         unsafe {
             ffi::metrohash128_2(bytes.as_ref().as_ptr() as *const u8,
@@ -154,7 +154,7 @@ impl FastHash for MetroHash64Crc_1 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u64 {
         let mut hash = 0_u64;
 
         unsafe {
@@ -181,7 +181,7 @@ impl FastHash for MetroHash64Crc_2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u64 {
         let mut hash = 0_u64;
 
         unsafe {
@@ -208,8 +208,8 @@ impl FastHash for MetroHash128Crc_1 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u128 {
-        let mut hash = u128::zero();
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
+        let mut hash = 0_u128;
 
         unsafe {
             ffi::metrohash128crc_1(bytes.as_ref().as_ptr() as *const u8,
@@ -235,8 +235,8 @@ impl FastHash for MetroHash128Crc_2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u128 {
-        let mut hash = u128::zero();
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
+        let mut hash = 0_u128;
 
         unsafe {
             ffi::metrohash128crc_2(bytes.as_ref().as_ptr() as *const u8,
@@ -255,7 +255,7 @@ impl_hasher_ext!(MetroHasher128Crc_2, MetroHash128Crc_2);
 /// `MetroHash` 64-bit hash function for a byte array.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     MetroHash64_1::hash(v)
 }
 
@@ -263,14 +263,14 @@ pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
     MetroHash64_1::hash_with_seed(v, seed)
 }
 
 /// `MetroHash` 128-bit hash function for a byte array.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     MetroHash128_1::hash(v)
 }
 
@@ -278,14 +278,14 @@ pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
 /// For convenience, a 128-bit seed is also hashed into the result.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u128 {
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     MetroHash128_1::hash_with_seed(v, seed)
 }
 
 /// `MetroHash` 64-bit hash function for a byte array using HW CRC instruction.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     MetroHash64Crc_1::hash(v)
 }
 
@@ -293,14 +293,23 @@ pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
     MetroHash64Crc_1::hash_with_seed(v, seed)
 }
 
+/// `MetroHash` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 /// `MetroHash` 128-bit hash function for a byte array using HW CRC instruction.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     MetroHash128Crc_1::hash(v)
 }
 
@@ -308,7 +317,7 @@ pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
 /// For convenience, a 128-bit seed is also hashed into the result.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u128 {
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     MetroHash128Crc_1::hash_with_seed(v, seed)
 }
 
@@ -316,8 +325,6 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u128 {
 mod tests {
     use std::hash::Hasher;
 
-    use extprim::u128::u128;
-
     use hasher::{FastHash, FastHasher, HasherExt};
     use super::*;
 
@@ -390,82 +397,82 @@ mod tests {
     #[test]
     fn test_metrohash128_1() {
         assert_eq!(MetroHash128_1::hash(b"hello"),
-                   u128::from_parts(3402816320040206173, 8267579177094204812));
+                   (3402816320040206173_u128 << 64) | 8267579177094204812);
         assert_eq!(MetroHash128_1::hash_with_seed(b"hello", 123),
-                   u128::from_parts(12815203692632715937, 16954909965332884860));
+                   (12815203692632715937_u128 << 64) | 16954909965332884860);
         assert_eq!(MetroHash128_1::hash(b"helloworld"),
-                   u128::from_parts(9114061290236148431, 9070923966242366617));
+                   (9114061290236148431_u128 << 64) | 9070923966242366617);
 
         let mut h = MetroHasher128_1::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(3402816320040206173, 8267579177094204812));
+                   (3402816320040206173_u128 << 64) | 8267579177094204812);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(9114061290236148431, 9070923966242366617));
+                   (9114061290236148431_u128 << 64) | 9070923966242366617);
     }
 
     #[test]
     fn test_metrohash128_2() {
         assert_eq!(MetroHash128_2::hash(b"hello"),
-                   u128::from_parts(8645868589955642073, 18321434607751955537));
+                   (8645868589955642073_u128 << 64) | 18321434607751955537);
         assert_eq!(MetroHash128_2::hash_with_seed(b"hello", 123),
-                   u128::from_parts(18306880534314458917, 13865612537680895339));
+                   (18306880534314458917_u128 << 64) | 13865612537680895339);
         assert_eq!(MetroHash128_2::hash(b"helloworld"),
-                   u128::from_parts(16062202743590172306, 17411406382482299971));
+                   (16062202743590172306_u128 << 64) | 17411406382482299971);
 
         let mut h = MetroHasher128_2::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(8645868589955642073, 18321434607751955537));
+                   (8645868589955642073_u128 << 64) | 18321434607751955537);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(16062202743590172306, 17411406382482299971));
+                   (16062202743590172306_u128 << 64) | 17411406382482299971);
     }
 
     #[cfg(feature = "sse42")]
     #[test]
     fn test_metrohash128crc_1() {
         assert_eq!(MetroHash128Crc_1::hash(b"hello"),
-                   u128::from_parts(16571975282436791800, 17425155230852563966));
+                   (16571975282436791800_u128 << 64) | 17425155230852563966);
         assert_eq!(MetroHash128Crc_1::hash_with_seed(b"hello", 123),
-                   u128::from_parts(2220453880884370284, 14239476408421791779));
+                   (2220453880884370284_u128 << 64) | 14239476408421791779);
         assert_eq!(MetroHash128Crc_1::hash(b"helloworld"),
-                   u128::from_parts(17933136491111761230, 16547792430792712607));
+                   (17933136491111761230_u128 << 64) | 16547792430792712607);
 
         let mut h = MetroHasher128Crc_1::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(16571975282436791800, 17425155230852563966));
+                   (16571975282436791800_u128 << 64) | 17425155230852563966);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(17933136491111761230, 16547792430792712607));
+                   (17933136491111761230_u128 << 64) | 16547792430792712607);
     }
 
     #[cfg(feature = "sse42")]
     #[test]
     fn test_metrohash128crc_2() {
         assert_eq!(MetroHash128Crc_2::hash(b"hello"),
-                   u128::from_parts(3913189466847962340, 14660650764275632263));
+                   (3913189466847962340_u128 << 64) | 14660650764275632263);
         assert_eq!(MetroHash128Crc_2::hash_with_seed(b"hello", 123),
-                   u128::from_parts(16592714704904777620, 13014106652212044248));
+                   (16592714704904777620_u128 << 64) | 13014106652212044248);
         assert_eq!(MetroHash128Crc_2::hash(b"helloworld"),
-                   u128::from_parts(18016644482327815926, 8337696161216335052));
+                   (18016644482327815926_u128 << 64) | 8337696161216335052);
 
         let mut h = MetroHasher128Crc_2::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(3913189466847962340, 14660650764275632263));
+                   (3913189466847962340_u128 << 64) | 14660650764275632263);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(18016644482327815926, 8337696161216335052));
+                   (18016644482327815926_u128 << 64) | 8337696161216335052);
     }
 }
@@ -0,0 +1,155 @@
+//! MinHash sketches for document similarity estimation.
+//!
+//! A `MinHashSketch` keeps, for each of its `num_hashes` seeds, the minimum hash
+//! of all the elements (usually the shingles of a document) it was updated with.
+//! The fraction of seeds for which two sketches agree estimates the Jaccard similarity
+//! of the two element sets.
+//!
+//! The hashes are derived from `farm::fingerprint64`, so the sketches are reproducible
+//! across platforms and may be compared between machines.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::minhash::MinHashSketch;
+//!
+//! let mut a = MinHashSketch::new(128);
+//! let mut b = MinHashSketch::new(128);
+//!
+//! a.update_shingles(b"the quick brown fox jumps over the lazy dog", 4);
+//! b.update_shingles(b"the quick brown fox jumps over the lazy dog", 4);
+//!
+//! assert_eq!(a.jaccard_estimate(&b), 1.0);
+//! ```
+//!
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use farm;
+
+/// A MinHash sketch of a set of byte arrays.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MinHashSketch {
+    mins: Vec<u64>,
+}
+
+impl MinHashSketch {
+    /// Constructs a new, empty `MinHashSketch` with `num_hashes` seeds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_hashes` is zero.
+    pub fn new(num_hashes: usize) -> Self {
+        assert!(num_hashes > 0, "number of hashes must be non-zero");
+
+        MinHashSketch { mins: vec![u64::MAX; num_hashes] }
+    }
+
+    /// Returns the number of seeds.
+    #[inline]
+    pub fn num_hashes(&self) -> usize {
+        self.mins.len()
+    }
+
+    /// Returns the minimum hash per seed.
+    #[inline]
+    pub fn mins(&self) -> &[u64] {
+        &self.mins
+    }
+
+    /// Adds an element to the sketch.
+    #[inline]
+    pub fn update(&mut self, element: &[u8]) {
+        self.update_fingerprint(farm::fingerprint64(element))
+    }
+
+    /// Adds every `k` bytes long shingle of `data` to the sketch, see `farm::shingle_hashes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn update_shingles(&mut self, data: &[u8], k: usize) {
+        for h in farm::shingle_hashes(data, k) {
+            self.update_fingerprint(h)
+        }
+    }
+
+    fn update_fingerprint(&mut self, fingerprint: u64) {
+        for (seed, min) in self.mins.iter_mut().enumerate() {
+            let h = farm::combine64(seed as u64, fingerprint);
+
+            if h < *min {
+                *min = h;
+            }
+        }
+    }
+
+    /// Estimates the Jaccard similarity of the element sets of two sketches,
+    /// as the fraction of seeds for which they have the same minimum hash.
+    ///
+    /// Two empty sketches are estimated to be identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches don't have the same number of seeds.
+    pub fn jaccard_estimate(&self, other: &MinHashSketch) -> f64 {
+        assert_eq!(self.mins.len(),
+                   other.mins.len(),
+                   "sketches must have the same number of hashes");
+
+        let same = self.mins.iter().zip(&other.mins).filter(|&(a, b)| a == b).count();
+
+        same as f64 / self.mins.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use farm;
+    use super::*;
+
+    fn sketch(data: &[u8]) -> MinHashSketch {
+        let mut s = MinHashSketch::new(128);
+
+        s.update_shingles(data, 4);
+        s
+    }
+
+    #[test]
+    fn test_minhash_sketch() {
+        let mut s = MinHashSketch::new(2);
+
+        assert_eq!(s.num_hashes(), 2);
+        assert_eq!(s.mins(), [u64::MAX, u64::MAX]);
+
+        s.update(b"hello word");
+        assert_eq!(s.mins(),
+                   [farm::combine64(0, 2862784602449412590),
+                    farm::combine64(1, 2862784602449412590)]);
+
+        let mut t = MinHashSketch::new(2);
+
+        t.update_shingles(b"hello word", 10);
+        assert_eq!(s, t);
+    }
+
+    #[test]
+    fn test_jaccard_estimate() {
+        let doc = b"the quick brown fox jumps over the lazy dog";
+
+        assert_eq!(sketch(doc).jaccard_estimate(&sketch(doc)), 1.0);
+        assert!(sketch(doc).jaccard_estimate(&sketch(b"0123456789012345678901234567")) < 0.05);
+
+        let half = sketch(b"abcdefghijklmnopqrstuvwxyz0123456789");
+        let other = sketch(b"stuvwxyz0123456789ABCDEFGHIJKLMNOPQR");
+        let estimate = half.jaccard_estimate(&other);
+
+        assert!(estimate > 0.1 && estimate < 0.6, "estimate {}", estimate);
+    }
+
+    #[test]
+    #[should_panic(expected = "sketches must have the same number of hashes")]
+    fn test_jaccard_estimate_size_mismatch() {
+        MinHashSketch::new(1).jaccard_estimate(&MinHashSketch::new(2));
+    }
+}
@@ -53,11 +53,13 @@
 //! ```
 //!
 #![allow(non_camel_case_types)]
-use std::os::raw::c_void;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `MumHash` 64-bit hash functions
 pub struct MumHash {}
@@ -67,7 +69,7 @@ impl FastHash for MumHash {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::mum_hash_(bytes.as_ref().as_ptr() as *const c_void,
                            bytes.as_ref().len(),
@@ -80,17 +82,26 @@ impl_hasher!(MumHasher, MumHash);
 
 /// `MumHash` 64-bit hash functions for a byte array.
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     MumHash::hash(v)
 }
 
 /// `MumHash` 64-bit hash function for a byte array.
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     MumHash::hash_with_seed(v, seed)
 }
 
+/// `MumHash` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use std::hash::Hasher;
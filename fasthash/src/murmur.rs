@@ -40,11 +40,13 @@
 //! assert_eq!(h, hash(&"hello world") as u32);
 //! ```
 //!
-use std::os::raw::c_void;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `MurmurHash` 32-bit hash functions
 pub struct Murmur {}
@@ -54,7 +56,7 @@ impl FastHash for Murmur {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::MurmurHash1(bytes.as_ref().as_ptr() as *const c_void,
                              bytes.as_ref().len() as i32,
@@ -73,7 +75,7 @@ impl FastHash for MurmurAligned {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::MurmurHash1Aligned(bytes.as_ref().as_ptr() as *const c_void,
                                     bytes.as_ref().len() as i32,
@@ -86,27 +88,27 @@ impl_hasher!(MurmurAlignedHasher, MurmurAligned);
 
 /// `MurmurHash` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     Murmur::hash(v)
 }
 
 /// `MurmurHash` 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Murmur::hash_with_seed(v, seed)
 }
 
 /// `MurmurHash` 32-bit aligned hash functions for a byte array.
 #[inline]
-pub fn hash32_aligned<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32_aligned<T: AsRef<[u8]>>(v: T) -> u32 {
     MurmurAligned::hash(v)
 }
 
 /// `MurmurHash` 32-bit aligned hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_aligned_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_aligned_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     MurmurAligned::hash_with_seed(v, seed)
 }
 
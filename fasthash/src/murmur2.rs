@@ -64,11 +64,13 @@
 //! ```
 //!
 #![allow(non_camel_case_types)]
-use std::os::raw::c_void;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `MurmurHash2` 32-bit hash functions
 pub struct Murmur2 {}
@@ -78,7 +80,7 @@ impl FastHash for Murmur2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::MurmurHash2(bytes.as_ref().as_ptr() as *const c_void,
                              bytes.as_ref().len() as i32,
@@ -97,7 +99,7 @@ impl FastHash for Murmur2A {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::MurmurHash2A(bytes.as_ref().as_ptr() as *const c_void,
                               bytes.as_ref().len() as i32,
@@ -116,7 +118,7 @@ impl FastHash for MurmurNeutral2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::MurmurHashNeutral2(bytes.as_ref().as_ptr() as *const c_void,
                                     bytes.as_ref().len() as i32,
@@ -136,7 +138,7 @@ impl FastHash for MurmurAligned2 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::MurmurHashAligned2(bytes.as_ref().as_ptr() as *const c_void,
                                     bytes.as_ref().len() as i32,
@@ -155,7 +157,7 @@ impl FastHash for Murmur2_x64_64 {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::MurmurHash64A(bytes.as_ref().as_ptr() as *const c_void,
                                bytes.as_ref().len() as i32,
@@ -174,7 +176,7 @@ impl FastHash for Murmur2_x86_64 {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::MurmurHash64B(bytes.as_ref().as_ptr() as *const c_void,
                                bytes.as_ref().len() as i32,
@@ -187,30 +189,39 @@ impl_hasher!(Murmur2Hasher_x86_64, Murmur2_x86_64);
 
 /// `MurmurHash2` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     Murmur2A::hash(v)
 }
 
 /// `MurmurHash2` 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Murmur2A::hash_with_seed(v, seed)
 }
 
 /// `MurmurHash2` 64-bit hash functions for a byte array.
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     Murmur2_x64_64::hash(v)
 }
 
 /// `MurmurHash2` 64-bit hash function for a byte array.
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     Murmur2_x64_64::hash_with_seed(v, seed)
 }
 
+/// `MurmurHash2` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use std::hash::Hasher;
@@ -24,20 +24,20 @@
 //!     s.finish()
 //! }
 //!
-//! let h = murmur3::hash128(b"hello world\xff").low64();
+//! let h = murmur3::hash128(b"hello world\xff") as u64;
 //!
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
 #![allow(non_camel_case_types)]
 use std::mem;
-use std::os::raw::c_void;
-
-use extprim::u128::u128;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `MurmurHash3` 32-bit hash functions
 pub struct Murmur3_x86_32 {}
@@ -47,7 +47,7 @@ impl FastHash for Murmur3_x86_32 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             let mut hash = 0_u32;
 
@@ -71,9 +71,9 @@ impl FastHash for Murmur3_x86_128 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u128 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
         unsafe {
-            let mut hash = u128::zero();
+            let mut hash = 0_u128;
 
             ffi::MurmurHash3_x86_128(bytes.as_ref().as_ptr() as *const c_void,
                                      bytes.as_ref().len() as i32,
@@ -95,9 +95,9 @@ impl FastHash for Murmur3_x64_128 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u128 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u128 {
         unsafe {
-            let mut hash = u128::zero();
+            let mut hash = 0_u128;
 
             ffi::MurmurHash3_x64_128(bytes.as_ref().as_ptr() as *const c_void,
                                      bytes.as_ref().len() as i32,
@@ -113,27 +113,27 @@ impl_hasher_ext!(Murmur3Hasher_x64_128, Murmur3_x64_128);
 
 /// `MurmurHash3` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     Murmur3_x86_32::hash(v)
 }
 
 /// `MurmurHash3` 32-bit hash functions for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     Murmur3_x86_32::hash_with_seed(v, seed)
 }
 
 /// `MurmurHash3` 128-bit hash functions for a byte array.
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     Murmur3_x64_128::hash(v)
 }
 
 /// `MurmurHash3` 128-bit hash functions for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u128 {
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     Murmur3_x64_128::hash_with_seed(v, seed)
 }
 
@@ -142,8 +142,6 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u128 {
 mod tests {
     use std::hash::Hasher;
 
-    use extprim::u128::u128;
-
     use hasher::{FastHash, FastHasher, HasherExt};
     use super::*;
 
@@ -165,40 +163,40 @@ mod tests {
     #[test]
     fn test_murmur3_x86_128() {
         assert_eq!(Murmur3_x86_128::hash(b"hello"),
-                   u128::from_parts(11158567162092401078, 15821672119091348640));
+                   (11158567162092401078_u128 << 64) | 15821672119091348640);
         assert_eq!(Murmur3_x86_128::hash_with_seed(b"hello", 123),
-                   u128::from_parts(2149221405153268091, 10130600740778964073));
+                   (2149221405153268091_u128 << 64) | 10130600740778964073);
         assert_eq!(Murmur3_x86_128::hash(b"helloworld"),
-                   u128::from_parts(4510970894511742178, 13166749202678098166));
+                   (4510970894511742178_u128 << 64) | 13166749202678098166);
 
         let mut h = Murmur3Hasher_x86_128::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(11158567162092401078, 15821672119091348640));
+                   (11158567162092401078_u128 << 64) | 15821672119091348640);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(4510970894511742178, 13166749202678098166));
+                   (4510970894511742178_u128 << 64) | 13166749202678098166);
     }
 
     #[test]
     fn test_murmur3_x64_128() {
         assert_eq!(Murmur3_x64_128::hash(b"hello"),
-                   u128::from_parts(6565844092913065241, 14688674573012802306));
+                   (6565844092913065241_u128 << 64) | 14688674573012802306);
         assert_eq!(Murmur3_x64_128::hash_with_seed(b"hello", 123),
-                   u128::from_parts(1043184066639555970, 3016954156110693643));
+                   (1043184066639555970_u128 << 64) | 3016954156110693643);
         assert_eq!(Murmur3_x64_128::hash(b"helloworld"),
-                   u128::from_parts(11724578221562109303, 10256632503372987514));
+                   (11724578221562109303_u128 << 64) | 10256632503372987514);
 
         let mut h = Murmur3Hasher_x64_128::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(6565844092913065241, 14688674573012802306));
+                   (6565844092913065241_u128 << 64) | 14688674573012802306);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(11724578221562109303, 10256632503372987514));
+                   (11724578221562109303_u128 << 64) | 10256632503372987514);
     }
 }
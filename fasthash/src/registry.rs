@@ -0,0 +1,123 @@
+//! A runtime registry of named 64-bit hash functions.
+//!
+//! `hash_by_name` dispatches to the built-in algorithms, named after their modules
+//! (`"city64"`, `"farm64"`, `"metro64"`, `"mum64"`, `"murmur2_64"`, `"sea64"`,
+//! `"spooky64"`, `"t1ha64"` and `"xx64"`), or to the custom ones added with
+//! `register_algorithm`, so plugins can provide their own hash.
+//!
+//! The registry is a process-wide global: a registered algorithm is visible to
+//! every caller, stays registered for the lifetime of the process, and a later
+//! registration under the same name replaces it. A custom algorithm also shadows
+//! the built-in one of the same name.
+//!
+//! A custom algorithm is called without holding the lock of the registry,
+//! so it may itself call `hash_by_name` or `register_algorithm`.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use fasthash::farm;
+//! use fasthash::registry::{hash_by_name, register_algorithm};
+//!
+//! register_algorithm("len", Arc::new(|data: &[u8]| data.len() as u64));
+//!
+//! assert_eq!(hash_by_name("len", b"hello"), Some(5));
+//! assert_eq!(hash_by_name("farm64", b"hello"), Some(farm::hash64(b"hello")));
+//! assert_eq!(hash_by_name("unknown", b"hello"), None);
+//! ```
+//!
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use city;
+use farm;
+use metro;
+use mum;
+use murmur2;
+use sea;
+use spooky;
+use t1ha;
+use xx;
+
+/// A custom 64-bit hash function.
+pub type HashFn = Arc<dyn Fn(&[u8]) -> u64 + Send + Sync>;
+
+static REGISTRY: RwLock<BTreeMap<String, HashFn>> = RwLock::new(BTreeMap::new());
+
+/// Registers a custom hash function under the name, replacing any previous one.
+pub fn register_algorithm(name: &str, f: HashFn) {
+    REGISTRY.write()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(name.to_owned(), f);
+}
+
+/// Hash the data with the registered or built-in algorithm of the name.
+///
+/// Returns `None` if no algorithm has this name.
+pub fn hash_by_name(name: &str, data: &[u8]) -> Option<u64> {
+    let f = REGISTRY.read().unwrap_or_else(|err| err.into_inner()).get(name).cloned();
+
+    if let Some(f) = f {
+        return Some(f(data));
+    }
+
+    builtin(name, data)
+}
+
+fn builtin(name: &str, data: &[u8]) -> Option<u64> {
+    Some(match name {
+        "city64" => city::hash64(data),
+        "farm64" => farm::hash64(data),
+        "metro64" => metro::hash64(data),
+        "mum64" => mum::hash64(data),
+        "murmur2_64" => murmur2::hash64(data),
+        "sea64" => sea::hash64(data),
+        "spooky64" => spooky::hash64(data),
+        "t1ha64" => t1ha::hash64(data),
+        "xx64" => xx::hash64(data),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use farm;
+    use xx;
+    use super::*;
+
+    #[test]
+    fn test_register_algorithm() {
+        assert_eq!(hash_by_name("test_xor", b"hello"), None);
+
+        register_algorithm("test_xor",
+                           Arc::new(|data: &[u8]| data.iter().fold(0, |h, &b| h ^ b as u64)));
+
+        assert_eq!(hash_by_name("test_xor", b"hello"), Some(0x62));
+        assert_eq!(hash_by_name("test_xor", b""), Some(0));
+
+        register_algorithm("test_xor", Arc::new(|_: &[u8]| 123));
+
+        assert_eq!(hash_by_name("test_xor", b"hello"), Some(123));
+    }
+
+    #[test]
+    fn test_reentrant_algorithm() {
+        register_algorithm("test_composed",
+                           Arc::new(|data: &[u8]| {
+            register_algorithm("test_inner", Arc::new(|data: &[u8]| data.len() as u64));
+
+            hash_by_name("farm64", data).unwrap() ^ hash_by_name("test_inner", data).unwrap()
+        }));
+
+        assert_eq!(hash_by_name("test_composed", b"hello"), Some(farm::hash64(b"hello") ^ 5));
+    }
+
+    #[test]
+    fn test_builtin_algorithms() {
+        assert_eq!(hash_by_name("farm64", b"hello"), Some(farm::hash64(b"hello")));
+        assert_eq!(hash_by_name("xx64", b"hello"), Some(xx::hash64(b"hello")));
+        assert_eq!(hash_by_name("md5", b"hello"), None);
+    }
+}
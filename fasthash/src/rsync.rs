@@ -0,0 +1,168 @@
+//! rsync-style block signatures.
+//!
+//! rsync matches the blocks of a file against a remote copy with a pair of checksums:
+//! a weak rolling checksum, cheap to slide one byte at a time over the data,
+//! and a strong hash confirming the candidates found with the weak one.
+//!
+//! `BlockSigner` computes the weak checksum described in Andrew Tridgell's thesis,
+//! and the `FarmHash` 128-bit fingerprint as the strong hash.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::rsync::{BlockSigner, RollingChecksum};
+//!
+//! let data = b"hello world";
+//! let signer = BlockSigner::new(4);
+//!
+//! let (weak, _strong) = signer.sign(&data[4..8]);
+//!
+//! let mut sum = RollingChecksum::new(&data[..4]);
+//!
+//! for i in 0..4 {
+//!     sum.roll(data[i], data[i + 4]);
+//! }
+//!
+//! assert_eq!(sum.value(), weak);
+//! ```
+//!
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use farm;
+
+/// The rsync weak rolling checksum over a fixed-size window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RollingChecksum {
+    a: u16,
+    b: u16,
+    len: usize,
+}
+
+impl RollingChecksum {
+    /// Computes the checksum of the initial window.
+    pub fn new(window: &[u8]) -> Self {
+        let len = window.len();
+        let (a, b) = window.iter()
+            .enumerate()
+            .fold((0_u16, 0_u16), |(a, b), (i, &x)| {
+                let weight = (len - i) as u16;
+
+                (a.wrapping_add(x as u16), b.wrapping_add(weight.wrapping_mul(x as u16)))
+            });
+
+        RollingChecksum {
+            a: a,
+            b: b,
+            len: len,
+        }
+    }
+
+    /// Returns the size of the window.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Slides the window one byte forward, removing `out` and appending `input`.
+    #[inline]
+    pub fn roll(&mut self, out: u8, input: u8) {
+        self.a = self.a.wrapping_sub(out as u16).wrapping_add(input as u16);
+        self.b = self.b
+            .wrapping_sub((self.len as u16).wrapping_mul(out as u16))
+            .wrapping_add(self.a);
+    }
+
+    /// Returns the checksum of the current window.
+    #[inline]
+    pub fn value(&self) -> u32 {
+        (self.a as u32) | ((self.b as u32) << 16)
+    }
+}
+
+/// Computes the rsync signature of the blocks, as a weak rolling checksum
+/// and a strong `FarmHash` 128-bit fingerprint pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockSigner {
+    block_size: usize,
+}
+
+impl BlockSigner {
+    /// Constructs a new `BlockSigner` for blocks of `block_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    #[inline]
+    pub fn new(block_size: usize) -> Self {
+        assert!(block_size > 0, "block size must be positive");
+
+        BlockSigner { block_size: block_size }
+    }
+
+    /// Returns the size of the blocks.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the weak checksum and the strong hash of a block.
+    #[inline]
+    pub fn sign(&self, block: &[u8]) -> (u32, u128) {
+        (RollingChecksum::new(block).value(), farm::fingerprint128(block))
+    }
+
+    /// Returns the signatures of the consecutive blocks of the data,
+    /// the last one may be shorter than `block_size`.
+    #[cfg(feature = "alloc")]
+    pub fn signatures(&self, data: &[u8]) -> Vec<(u32, u128)> {
+        data.chunks(self.block_size).map(|block| self.sign(block)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use farm;
+    use super::*;
+
+    #[test]
+    fn test_rolling_checksum() {
+        assert_eq!(RollingChecksum::new(b"").value(), 0);
+        assert_eq!(RollingChecksum::new(b"\x01\x02").value(),
+                   3 | (4 << 16));
+
+        let data = (0..1024_u32).map(|i| (i * 7 + i / 3) as u8).collect::<Vec<u8>>();
+        let window = 64;
+
+        let mut sum = RollingChecksum::new(&data[..window]);
+
+        assert_eq!(sum.len(), window);
+
+        for i in 0..data.len() - window {
+            sum.roll(data[i], data[i + window]);
+
+            assert_eq!(sum, RollingChecksum::new(&data[i + 1..i + 1 + window]));
+        }
+    }
+
+    #[test]
+    fn test_block_signer() {
+        let signer = BlockSigner::new(4);
+        let (weak, strong) = signer.sign(b"hello");
+
+        assert_eq!(weak, RollingChecksum::new(b"hello").value());
+        assert_eq!(strong, farm::fingerprint128(b"hello"));
+
+        let signatures = signer.signatures(b"hello world");
+
+        assert_eq!(signatures.len(), 3);
+        assert_eq!(signatures[0], signer.sign(b"hell"));
+        assert_eq!(signatures[2], signer.sign(b"rld"));
+    }
+}
@@ -29,7 +29,11 @@ use seahash;
 
 pub use seahash::{SeaHasher as SeaHasher64, hash as hash64, hash_seeded as hash_with_seeds};
 
-use hasher::{FastHash, FastHasher, StreamHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
+#[cfg(feature = "std")]
+use hasher::StreamHasher;
 
 /// `SeaHash` 64-bit hash functions
 pub struct SeaHash {}
@@ -39,18 +43,19 @@ impl FastHash for SeaHash {
     type Seed = (u64, u64, u64, u64);
 
     #[inline]
-    fn hash<T: AsRef<[u8]>>(bytes: &T) -> u64 {
+    fn hash<T: AsRef<[u8]>>(bytes: T) -> u64 {
         seahash::hash(bytes.as_ref())
     }
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: (u64, u64, u64, u64)) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: (u64, u64, u64, u64)) -> u64 {
         seahash::hash_seeded(bytes.as_ref(), seed.0, seed.1, seed.2, seed.3)
     }
 }
 
 impl_fasthash!(SeaHasher64, SeaHash);
 
+#[cfg(feature = "alloc")]
 impl FastHasher for SeaHasher64 {
     type Seed = (u64, u64, u64, u64);
 
@@ -65,6 +70,7 @@ impl FastHasher for SeaHasher64 {
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for SeaHasher64 {}
 
 #[cfg(test)]
@@ -41,19 +41,41 @@
 //!
 //! let h = spooky::hash128(b"hello world\xff");
 //!
-//! assert_eq!(h.low64(), hash(&"hello world"));
+//! assert_eq!(h as u64, hash(&"hello world"));
 //! ```
 //!
-use std::hash::Hasher;
-use std::os::raw::c_void;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "alloc")]
+use std::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::ops::Deref;
+#[cfg(feature = "alloc")]
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ffi::c_void;
+#[cfg(feature = "zeroize")]
+use std::ptr;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
-use extprim::u128::u128;
+#[cfg(feature = "std")]
+use rand::{OsRng, Rng};
+#[cfg(all(feature = "alloc", feature = "digest"))]
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update};
+#[cfg(all(feature = "alloc", feature = "digest"))]
+use digest::consts::U16;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher, HasherExt, StreamHasher};
+use hasher::{FastHash, u128_from_parts, u128_to_parts};
+#[cfg(feature = "alloc")]
+use hasher::{FastHasher, HasherExt};
+#[cfg(feature = "std")]
+use hasher::StreamHasher;
 
 /// `SpookyHash` 32-bit hash functions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct SpookyHash32 {}
 
 impl FastHash for SpookyHash32 {
@@ -61,7 +83,7 @@ impl FastHash for SpookyHash32 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         let mut hash1 = seed as u64;
         let mut hash2 = seed as u64;
 
@@ -79,14 +101,24 @@ impl FastHash for SpookyHash32 {
 impl_fasthash!(SpookyHasher128, SpookyHash32);
 
 /// `SpookyHash` 64-bit hash functions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct SpookyHash64 {}
 
+impl SpookyHash64 {
+    /// Hash functions for a byte array, returning the hash as little-endian bytes.
+    #[inline]
+    #[must_use]
+    pub fn hash_array<T: AsRef<[u8]>>(bytes: T) -> [u8; 8] {
+        SpookyHash64::hash(bytes).to_le_bytes()
+    }
+}
+
 impl FastHash for SpookyHash64 {
     type Value = u64;
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         let mut hash1 = seed;
         let mut hash2 = seed;
 
@@ -104,138 +136,873 @@ impl FastHash for SpookyHash64 {
 impl_fasthash!(SpookyHasher128, SpookyHash64);
 
 /// `SpookyHash` 128-bit hash functions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct SpookyHash128 {}
 
+impl SpookyHash128 {
+    /// Hash functions for a byte array, seeded from 16 bytes.
+    ///
+    /// The seed is read as a little-endian `u128`, i.e. the first 8 bytes are
+    /// the low half and the last 8 bytes the high half, then passed to `hash_with_seed`.
+    #[inline]
+    #[must_use]
+    pub fn hash_with_seed_bytes<T: AsRef<[u8]>>(bytes: T, seed: &[u8; 16]) -> u128 {
+        SpookyHash128::hash_with_seed(bytes, u128::from_le_bytes(*seed))
+    }
+
+    /// Hash functions for a byte array, writing the hash into `out` as little-endian bytes.
+    #[inline]
+    pub fn hash_into<T: AsRef<[u8]>>(bytes: T, out: &mut [u8; 16]) {
+        *out = SpookyHash128::hash_array(bytes);
+    }
+
+    /// Hash functions for a byte array, returning the hash as little-endian bytes.
+    #[inline]
+    #[must_use]
+    pub fn hash_array<T: AsRef<[u8]>>(bytes: T) -> [u8; 16] {
+        SpookyHash128::hash(bytes).to_le_bytes()
+    }
+}
+
 impl FastHash for SpookyHash128 {
     type Value = u128;
     type Seed = u128;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u128) -> u128 {
-        let mut hash1 = seed.high64();
-        let mut hash2 = seed.low64();
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u128) -> u128 {
+        let (hash1, hash2) = hash128_parts(bytes.as_ref(), seed);
+
+        u128_from_parts(hash1, hash2)
+    }
+}
+
+/// An implementation of `std::hash::Hasher` and `fasthash::HasherExt`.
+///
+/// `Hasher::finish` truncates the 128-bit hash to its low 64 bits, to fit the trait;
+/// call `finish_ext` for the full value, or `finish_truncated` to make the truncation explicit.
+///
+/// For keyed use, the `zeroize` feature overwrites the state and the seed on drop.
+///
+/// # Thread safety
+///
+/// A `SpookyHasher128` is `Send`, so a partially fed hasher may be moved to another thread
+/// and finished there. It is deliberately not `Sync`: `finish` and `finish_ext` take `&self`,
+/// but the C++ `SpookyHash::Final` pads the buffered block in place,
+/// so a hasher must not be shared between threads.
+///
+/// ```compile_fail
+/// fn assert_sync<T: Sync>() {}
+///
+/// assert_sync::<fasthash::SpookyHasher>();
+/// ```
+///
+/// # Await safety
+///
+/// The `SpookyHash` state lives in a fixed-size heap allocation, so a `SpookyHasher128`
+/// is `Unpin` and `Send`, and may be held across `.await` points while hashing a stream
+/// chunk by chunk, with a memory use independent of the stream length.
+/// The buffering hashers, like `FarmHasher64`, may be held across `.await` points too,
+/// but keep every written byte until they are dropped.
+///
+/// ```edition2018
+/// use std::future::Future;
+/// use std::hash::Hasher;
+/// use std::pin::Pin;
+/// use std::ptr;
+/// use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+///
+/// use fasthash::{spooky, FastHasher, SpookyHasher};
+///
+/// // A future which is pending once before it completes, like an I/O read.
+/// struct Chunk<'a>(&'a [u8], bool);
+///
+/// impl<'a> Future for Chunk<'a> {
+///     type Output = &'a [u8];
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<&'a [u8]> {
+///         if self.1 {
+///             Poll::Ready(self.0)
+///         } else {
+///             self.1 = true;
+///             cx.waker().wake_by_ref();
+///             Poll::Pending
+///         }
+///     }
+/// }
+///
+/// async fn hash_stream(chunks: &[&[u8]]) -> u64 {
+///     let mut h = SpookyHasher::new();
+///
+///     for &chunk in chunks {
+///         h.write(Chunk(chunk, false).await);
+///     }
+///
+///     h.finish()
+/// }
+///
+/// // A waker which does nothing, since the future is polled in a loop.
+/// fn noop_raw_waker() -> RawWaker {
+///     fn clone(_: *const ()) -> RawWaker {
+///         noop_raw_waker()
+///     }
+///     fn noop(_: *const ()) {}
+///
+///     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+///
+///     RawWaker::new(ptr::null(), &VTABLE)
+/// }
+///
+/// let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+/// let mut fut = Box::pin(hash_stream(&[b"hello", b" ", b"world"]));
+/// let mut cx = Context::from_waker(&waker);
+///
+/// let h = loop {
+///     if let Poll::Ready(h) = fut.as_mut().poll(&mut cx) {
+///         break h;
+///     }
+/// };
+///
+/// assert_eq!(h, spooky::hash128(b"hello world") as u64);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct SpookyHasher128 {
+    state: *mut c_void,
+    seed: (u64, u64),
+}
+
+// The state is owned exclusively by the hasher, which frees it on drop, and is only accessed
+// through `&self` or `&mut self`. The C++ `SpookyHash` object has no thread affinity, and
+// the library no global state, so the hasher may move between threads. `Sync` isn't
+// implemented, since `SpookyHasherFinal` writes to the state through `&self`.
+#[cfg(feature = "alloc")]
+unsafe impl Send for SpookyHasher128 {}
+
+#[cfg(feature = "alloc")]
+impl SpookyHasher128 {
+    /// Constructs a new `SpookyHasher128`, or an `AllocError` if the state can't be allocated.
+    ///
+    /// `new` and `with_seed` panic instead.
+    #[inline]
+    pub fn try_new() -> Result<Self, AllocError> {
+        SpookyHasher128::try_with_seed((0, 0))
+    }
+
+    /// Constructs a new `SpookyHasher128` with seed,
+    /// or an `AllocError` if the state can't be allocated.
+    ///
+    /// `SpookyHasherNew` returns a null pointer when the allocation fails,
+    /// which is checked before the state is initialized.
+    pub fn try_with_seed(seed: (u64, u64)) -> Result<Self, AllocError> {
+        let h = unsafe { ffi::SpookyHasherNew() };
+
+        if h.is_null() {
+            return Err(AllocError);
+        }
 
         unsafe {
-            ffi::SpookyHasherHash(bytes.as_ref().as_ptr() as *const c_void,
-                                  bytes.as_ref().len(),
-                                  &mut hash1,
-                                  &mut hash2);
+            ffi::SpookyHasherInit(h, seed.0, seed.1);
         }
 
-        u128::from_parts(hash1, hash2)
+        Ok(SpookyHasher128 {
+            state: h,
+            seed: seed,
+        })
     }
-}
 
-/// An implementation of `std::hash::Hasher` and `fasthash::HasherExt`.
-pub struct SpookyHasher128(*mut c_void);
+    /// Restarts the hash with the seed of this hasher, reusing the allocated state.
+    #[inline]
+    pub fn reset(&mut self) {
+        let (seed1, seed2) = self.seed;
+
+        self.reset_with_seeds(seed1, seed2)
+    }
+
+    /// Restarts the hash with a 64-bit seed, reusing the allocated state.
+    ///
+    /// The seed is used as both halves of the `SpookyHash` seed, like `SpookyHash64`.
+    #[inline]
+    pub fn reset_with_seed(&mut self, seed: u64) {
+        self.reset_with_seeds(seed, seed)
+    }
+
+    /// Restarts the hash with a 128-bit seed, reusing the allocated state.
+    ///
+    /// The seed is split into its high and low halves, like `SpookyHash128`.
+    #[inline]
+    pub fn reset_with_seed128(&mut self, seed: u128) {
+        let (high, low) = u128_to_parts(seed);
+
+        self.reset_with_seeds(high, low)
+    }
+
+    /// Returns the two 64-bit words of the 128-bit hash, `(hash1, hash2)`,
+    /// which are the high and low halves of `finish_ext`.
+    ///
+    /// They are computed with the seed of the hasher for inputs of any length,
+    /// so they are `hash128_parts` of the written bytes and the same seed.
+    #[inline]
+    #[must_use]
+    pub fn finish_ext_parts(&self) -> (u64, u64) {
+        let mut hash1 = 0_u64;
+        let mut hash2 = 0_u64;
+
+        unsafe {
+            ffi::SpookyHasherFinal(self.state, &mut hash1, &mut hash2);
+        }
+
+        (hash1, hash2)
+    }
+
+    /// Returns the 32-bit hash, the low 32 bits of `hash1`,
+    /// which matches `SpookyHash32` for the same input and seed.
+    #[inline]
+    #[must_use]
+    pub fn finish32(&self) -> u32 {
+        self.finish_ext_parts().0 as u32
+    }
+
+    /// Returns the 64-bit hash, `hash1`, the high half of `finish_ext`.
+    ///
+    /// For a hasher seeded with `(seed, seed)`, this is `SpookyHash64::hash_with_seed`
+    /// of the written bytes. `Hasher::finish` returns the low half, `hash2`, instead.
+    #[inline]
+    #[must_use]
+    pub fn finish64(&self) -> u64 {
+        self.finish_ext_parts().0
+    }
 
+    /// Returns the low 64 bits of `finish_ext`, which is what `Hasher::finish` returns.
+    ///
+    /// The same as `finish`, for call sites which truncate the 128-bit hash on purpose.
+    #[inline]
+    #[must_use]
+    pub fn finish_truncated(&self) -> u64 {
+        self.finish_ext() as u64
+    }
+
+    /// Serializes the hash state, to resume hashing the same input later with `restore`,
+    /// e.g. in another process.
+    ///
+    /// The snapshot holds the C++ `SpookyHash` state followed by the little-endian seed.
+    /// The layout of the state depends on the pointer width and the byte order, so it may
+    /// only be restored on the same platform.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let size = unsafe { ffi::SpookyHasherStateSize() };
+        let mut bytes = vec![0; size];
+
+        unsafe {
+            ffi::SpookyHasherSnapshot(self.state, bytes.as_mut_ptr() as *mut c_void);
+        }
+
+        bytes.extend_from_slice(&self.seed.0.to_le_bytes());
+        bytes.extend_from_slice(&self.seed.1.to_le_bytes());
+        bytes
+    }
+
+    /// Constructs a new `SpookyHasher128` with the hash state of a `snapshot`.
+    ///
+    /// Returns an `InvalidState` error if the bytes aren't a snapshot of this platform,
+    /// or if the buffered length is inconsistent with the total length.
+    pub fn restore(bytes: &[u8]) -> Result<Self, InvalidState> {
+        let size = unsafe { ffi::SpookyHasherStateSize() };
+
+        if bytes.len() != size + 16 {
+            return Err(InvalidState);
+        }
+
+        let (state, seed) = bytes.split_at(size);
+        let mut seed1 = [0; 8];
+        let mut seed2 = [0; 8];
+
+        seed1.copy_from_slice(&seed[..8]);
+        seed2.copy_from_slice(&seed[8..]);
+
+        let h = SpookyHasher128::with_seed((u64::from_le_bytes(seed1), u64::from_le_bytes(seed2)));
+
+        if unsafe { ffi::SpookyHasherRestore(h.state, state.as_ptr() as *const c_void, size) } {
+            Ok(h)
+        } else {
+            Err(InvalidState)
+        }
+    }
+
+    #[inline]
+    fn reset_with_seeds(&mut self, seed1: u64, seed2: u64) {
+        unsafe {
+            ffi::SpookyHasherInit(self.state, seed1, seed2);
+        }
+
+        self.seed = (seed1, seed2);
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl Default for SpookyHasher128 {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Clone for SpookyHasher128 {
+    #[inline]
+    fn clone(&self) -> Self {
+        let state = unsafe { ffi::SpookyHasherClone(self.state) };
+
+        assert!(!state.is_null(), "failed to allocate the SpookyHash state");
+
+        SpookyHasher128 {
+            state: state,
+            seed: self.seed,
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "zeroize")))]
 impl Drop for SpookyHasher128 {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::SpookyHasherFree(self.0) }
+        unsafe { ffi::SpookyHasherFree(self.state) }
     }
 }
 
+// With the `zeroize` feature, the state and the seed, which may be key material,
+// are overwritten before the memory is freed.
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+impl Drop for SpookyHasher128 {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::SpookyHasherZero(self.state);
+            ffi::SpookyHasherFree(self.state);
+
+            ptr::write_volatile(&mut self.seed, (0, 0));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl Hasher for SpookyHasher128 {
     #[inline]
     fn finish(&self) -> u64 {
-        self.finish_ext().low64()
+        self.finish_truncated()
     }
 
     #[inline]
     fn write(&mut self, bytes: &[u8]) {
         unsafe {
-            ffi::SpookyHasherUpdate(self.0,
+            ffi::SpookyHasherUpdate(self.state,
                                     bytes.as_ref().as_ptr() as *const c_void,
                                     bytes.as_ref().len())
         }
     }
 }
 
+// Bytes are collected in a stack buffer, to make one FFI call per 64 bytes rather than per byte.
+#[cfg(feature = "alloc")]
+impl Extend<u8> for SpookyHasher128 {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let mut buf = [0; 64];
+        let mut len = 0;
+
+        for b in iter {
+            buf[len] = b;
+            len += 1;
+
+            if len == buf.len() {
+                self.write(&buf);
+                len = 0;
+            }
+        }
+
+        if len > 0 {
+            self.write(&buf[..len]);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Extend<&'a [u8]> for SpookyHasher128 {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+        for bytes in iter {
+            self.write(bytes)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl HasherExt for SpookyHasher128 {
     #[inline]
     fn finish_ext(&self) -> u128 {
-        let mut hash1 = 0_u64;
-        let mut hash2 = 0_u64;
+        let (hash1, hash2) = self.finish_ext_parts();
 
-        unsafe {
-            ffi::SpookyHasherFinal(self.0, &mut hash1, &mut hash2);
-        }
-
-        u128::from_parts(hash1, hash2)
+        u128_from_parts(hash1, hash2)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl FastHasher for SpookyHasher128 {
     type Seed = (u64, u64);
 
     #[inline]
     fn with_seed(seed: Self::Seed) -> SpookyHasher128 {
-        let h = unsafe { ffi::SpookyHasherNew() };
-
-        unsafe {
-            ffi::SpookyHasherInit(h, seed.0, seed.1);
-        }
-
-        SpookyHasher128(h)
+        SpookyHasher128::try_with_seed(seed).expect("failed to allocate the SpookyHash state")
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for SpookyHasher128 {}
 
+/// Pipes the written bytes into the hasher, e.g. with `io::copy`, without buffering them.
+#[cfg(feature = "std")]
+impl io::Write for SpookyHasher128 {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Hasher::write(self, buf);
+
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl_fasthash!(SpookyHasher128, SpookyHash128);
 
+/// A running `SpookyHash` 128-bit hash of an append-only log.
+///
+/// `SpookyHasher128::finish_ext` doesn't consume the streaming state, so the hash of
+/// the whole log can be peeked after each append, and every `append` costs
+/// O(record) rather than rehashing the full log.
+///
+/// # Example
+///
+/// ```
+/// use fasthash::spooky::{self, LogHasher};
+///
+/// let mut log = LogHasher::new();
+///
+/// log.append(b"hello");
+///
+/// assert_eq!(log.append(b"world"), spooky::hash128(b"helloworld"));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct LogHasher {
+    hasher: SpookyHasher128,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl LogHasher {
+    /// Constructs a new `LogHasher` for an empty log.
+    #[inline]
+    pub fn new() -> Self {
+        LogHasher::default()
+    }
+
+    /// Appends a record, returning the hash of the whole log so far.
+    #[inline]
+    pub fn append(&mut self, record: &[u8]) -> u128 {
+        self.hasher.write(record);
+        self.len += record.len();
+
+        self.hasher.finish_ext()
+    }
+
+    /// Returns the hash of the whole log so far.
+    #[inline]
+    #[must_use]
+    pub fn hash(&self) -> u128 {
+        self.hasher.finish_ext()
+    }
+
+    /// Returns the number of bytes appended to the log.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing was appended to the log.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A `digest::Digest` adapter for `SpookyHasher128`, for the code generic over
+/// the RustCrypto hash traits.
+///
+/// The 16 bytes output is the little-endian `finish_ext`. `SpookyHash` is a
+/// non-cryptographic hash, it must not be used where a cryptographic digest is required.
+///
+/// # Example
+///
+/// ```
+/// # extern crate digest;
+/// # extern crate fasthash;
+/// use digest::Digest;
+/// use fasthash::spooky::{self, SpookyDigest};
+///
+/// # fn main() {
+/// let h = SpookyDigest::digest(b"hello");
+///
+/// assert_eq!(h[..], spooky::hash128(b"hello").to_le_bytes()[..]);
+/// # }
+/// ```
+#[cfg(all(feature = "alloc", feature = "digest"))]
+#[derive(Clone, Default)]
+pub struct SpookyDigest(SpookyHasher128);
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl SpookyDigest {
+    /// Constructs a new `SpookyDigest` with seed.
+    #[inline]
+    pub fn with_seed(seed: (u64, u64)) -> Self {
+        SpookyDigest(SpookyHasher128::with_seed(seed))
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl HashMarker for SpookyDigest {}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl OutputSizeUser for SpookyDigest {
+    type OutputSize = U16;
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl Update for SpookyDigest {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.0.write(data)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl FixedOutput for SpookyDigest {
+    #[inline]
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.0.finish_ext().to_le_bytes())
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl Reset for SpookyDigest {
+    #[inline]
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl FixedOutputReset for SpookyDigest {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.0.finish_ext().to_le_bytes());
+        self.0.reset()
+    }
+}
+
+/// The error of failing to allocate the state of a `SpookyHasher128`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError;
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("failed to allocate the SpookyHash state")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AllocError {}
+
+/// The error of restoring a `SpookyHasher128` from bytes which aren't a valid snapshot.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidState;
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for InvalidState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid SpookyHash state snapshot")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for InvalidState {}
+
+/// A `BuildHasher` creating `SpookyHasher`s seeded from a stored seed.
+///
+/// The `Default` seed is zero, so maps are reproducible unless built with `random`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpookyBuildHasher {
+    seed: (u64, u64),
+}
+
+#[cfg(feature = "alloc")]
+impl SpookyBuildHasher {
+    /// Constructs a new `SpookyBuildHasher` with the given seed.
+    #[inline]
+    pub fn with_seed(seed: (u64, u64)) -> Self {
+        SpookyBuildHasher { seed: seed }
+    }
+
+    /// Constructs a new `SpookyBuildHasher` with a seed drawn once from the OS RNG.
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        let mut rng = OsRng::new().expect("failed to create an OS RNG");
+
+        SpookyBuildHasher::with_seed(rng.gen())
+    }
+
+    /// Returns the seed of the built hashers.
+    #[inline]
+    pub fn seed(&self) -> (u64, u64) {
+        self.seed
+    }
+
+    /// Calculates the 128-bit hash of a single value.
+    ///
+    /// This is the 128-bit counterpart of `BuildHasher::hash_one`, finished with `finish_ext`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::{BuildHasher, Hash};
+    /// use fasthash::HasherExt;
+    /// use fasthash::spooky::SpookyBuildHasher;
+    ///
+    /// let spooky_build = SpookyBuildHasher::with_seed((123, 456));
+    /// let mut h = spooky_build.build_hasher();
+    ///
+    /// 42u64.hash(&mut h);
+    ///
+    /// assert_eq!(spooky_build.hash_one_ext(&42u64), h.finish_ext());
+    /// assert_eq!(spooky_build.hash_one(&42u64), h.finish_ext() as u64);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn hash_one_ext<T: Hash + ?Sized>(&self, value: &T) -> u128 {
+        let mut h = self.build_hasher();
+
+        value.hash(&mut h);
+        h.finish_ext()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BuildHasher for SpookyBuildHasher {
+    type Hasher = SpookyHasher128;
+
+    #[inline]
+    fn build_hasher(&self) -> SpookyHasher128 {
+        SpookyHasher128::with_seed(self.seed)
+    }
+}
+
 /// `SpookyHash` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+#[must_use]
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     SpookyHash32::hash(v)
 }
 
 /// `SpookyHash` 32-bit hash functions for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+#[must_use]
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     SpookyHash32::hash_with_seed(v, seed)
 }
 
+/// `SpookyHash` 32-bit hash functions for a byte array, with a 64-bit seed.
+///
+/// The full seed is passed into both words and the result is the low 32 bits of `hash1`,
+/// i.e. `hash64_with_seed(bytes, seed) as u32`. `hash32_with_seed` widens its `u32` seed
+/// the same way, so both agree for the seeds below `2^32`, while two 64-bit seeds
+/// sharing their low 32 bits are told apart here.
+#[inline]
+#[must_use]
+pub fn hash32_with_seed64(bytes: &[u8], seed: u64) -> u32 {
+    SpookyHash64::hash_with_seed(bytes, seed) as u32
+}
+
 /// `SpookyHash` 64-bit hash functions for a byte array.
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+#[must_use]
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     SpookyHash64::hash(v)
 }
 
+/// `SpookyHash` 64-bit hash functions for a batch of independent byte arrays.
+///
+/// The result holds `hash64(&items[i])` at index `i`. The items are hashed on the
+/// `rayon` thread pool when the `rayon` feature is enabled, and sequentially otherwise.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_batch<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<u64> {
+    batch_hashes(items)
+}
+
+#[cfg(all(feature = "alloc", feature = "rayon"))]
+fn batch_hashes<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<u64> {
+    use rayon::prelude::*;
+
+    items.par_iter().map(SpookyHash64::hash).collect()
+}
+
+#[cfg(all(feature = "alloc", not(feature = "rayon")))]
+fn batch_hashes<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<u64> {
+    items.iter().map(SpookyHash64::hash).collect()
+}
+
+/// A `SpookyHash` 64-bit hash value, which can't be compared with other algorithms' values.
+///
+/// It derefs to `u64` for arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpookyHash64Value(pub u64);
+
+impl Deref for SpookyHash64Value {
+    type Target = u64;
+
+    #[inline]
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+/// `SpookyHash` 64-bit hash functions for a byte array, returning a typed value.
+#[inline]
+#[must_use]
+pub fn hash64_typed<T: AsRef<[u8]>>(v: T) -> SpookyHash64Value {
+    SpookyHash64Value(hash64(v))
+}
+
 /// `SpookyHash` 64-bit hash functions for a byte array.
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+#[must_use]
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     SpookyHash64::hash_with_seed(v, seed)
 }
 
+/// `SpookyHash` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+#[must_use]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 /// `SpookyHash` 128-bit hash functions for a byte array.
 /// For convenience, a 128-bit seed is also hashed into the result.
 #[inline]
-pub fn hash128<T: AsRef<[u8]>>(v: &T) -> u128 {
+#[must_use]
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     SpookyHash128::hash(v)
 }
 
 /// `SpookyHash` 128-bit hash functions for a byte array.
 #[inline]
-pub fn hash128_with_seed<T: AsRef<[u8]>>(v: &T, seed: u128) -> u128 {
+#[must_use]
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
     SpookyHash128::hash_with_seed(v, seed)
 }
 
+/// `SpookyHash` 128-bit hash functions for a byte array, returning the two 64-bit words.
+///
+/// The seed is split like `SpookyHash128`, and the result is `(hash1, hash2)`,
+/// the high and low halves of `hash128_with_seed`.
+#[inline]
+#[must_use]
+pub fn hash128_parts(bytes: &[u8], seed: u128) -> (u64, u64) {
+    let (mut hash1, mut hash2) = u128_to_parts(seed);
+
+    unsafe {
+        ffi::SpookyHasherHash(bytes.as_ptr() as *const c_void,
+                              bytes.len(),
+                              &mut hash1,
+                              &mut hash2);
+    }
+
+    (hash1, hash2)
+}
+
+/// `SpookyHash` 64-bit hash functions for scattered buffers, e.g. a header and a body.
+///
+/// The chunks are written in order to a seeded `SpookyHasher`, so the result is
+/// `hash64_with_seed` of their concatenation, without concatenating them.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash64_vectored(chunks: &[&[u8]], seed: u64) -> u64 {
+    let mut h = SpookyHasher128::with_seed((seed, seed));
+
+    h.extend(chunks.iter().cloned());
+    h.finish_ext_parts().0
+}
+
+/// `SpookyHash` 128-bit hash functions for scattered buffers, e.g. a header and a body.
+///
+/// The chunks are written in order to a seeded `SpookyHasher128`, so the result is
+/// `hash128_with_seed` of their concatenation, without concatenating them.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash128_vectored(chunks: &[&[u8]], seed: u128) -> u128 {
+    let mut h = SpookyHasher128::with_seed(u128_to_parts(seed));
+
+    h.extend(chunks.iter().cloned());
+    h.finish_ext()
+}
+
+/// `SpookyHash` 64-bit hash functions for `len` bytes at `ptr`.
+///
+/// The same as `hash64`, for callers holding a raw pointer, e.g. to foreign-owned memory.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes.
+#[inline]
+#[must_use]
+pub unsafe fn hash64_raw(ptr: *const u8, len: usize) -> u64 {
+    let mut hash1 = 0;
+    let mut hash2 = 0;
+
+    ffi::SpookyHasherHash(ptr as *const c_void, len, &mut hash1, &mut hash2);
+
+    hash1
+}
+
+/// `SpookyHash` 128-bit hash functions for `len` bytes at `ptr`.
+///
+/// The same as `hash128`, for callers holding a raw pointer, e.g. to foreign-owned memory.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes.
+#[inline]
+#[must_use]
+pub unsafe fn hash128_raw(ptr: *const u8, len: usize) -> u128 {
+    let mut hash1 = 0;
+    let mut hash2 = 0;
+
+    ffi::SpookyHasherHash(ptr as *const c_void, len, &mut hash1, &mut hash2);
+
+    u128_from_parts(hash1, hash2)
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
     use std::hash::Hasher;
-
-    use extprim::u128::u128;
+    use std::thread;
 
     use hasher::{FastHash, FastHasher, HasherExt, StreamHasher};
     use super::*;
@@ -247,34 +1014,553 @@ mod tests {
         assert_eq!(SpookyHash32::hash(b"helloworld"), 3874077464);
     }
 
+    #[test]
+    fn test_hash32_with_seed64() {
+        assert_eq!(hash32_with_seed64(b"hello", 123), hash32_with_seed(b"hello", 123));
+        assert_eq!(hash32_with_seed64(b"hello", (1 << 32) | 123), 2173884975);
+        assert_eq!(hash32_with_seed64(b"hello", (1 << 32) | 123),
+                   hash64_with_seed(b"hello", (1 << 32) | 123) as u32);
+    }
+
     #[test]
     fn test_spooky64() {
         assert_eq!(SpookyHash64::hash(b"hello"), 6105954949053820864);
         assert_eq!(SpookyHash64::hash_with_seed(b"hello", 123),
                    8819086853393477700);
         assert_eq!(SpookyHash64::hash(b"helloworld"), 18412934266828208920);
+        assert_eq!(u64::from_le_bytes(SpookyHash64::hash_array(b"hello")), hash64(b"hello"));
+        assert_eq!(SpookyHash64::hash_array(b"hello"),
+                   6105954949053820864_u64.to_le_bytes());
+        assert_eq!(hash64_str("hello"), 6105954949053820864);
+        assert_eq!(hash64_str("hello"), hash64("hello"));
+    }
+
+    #[test]
+    fn test_hash_raw() {
+        let data = b"hello".to_vec();
+
+        unsafe {
+            assert_eq!(hash64_raw(data.as_ptr(), data.len()), 6105954949053820864);
+            assert_eq!(hash64_raw(data.as_ptr(), data.len()), hash64(&data));
+            assert_eq!(hash128_raw(data.as_ptr(), data.len()), hash128(&data));
+            assert_eq!(hash64_raw(data.as_ptr(), 0), hash64(b""));
+        }
+    }
+
+    #[test]
+    fn test_hash64_batch() {
+        let items = (0..10_000).map(|i| format!("key-{}", i)).collect::<Vec<String>>();
+        let hashes = hash64_batch(&items);
+
+        assert_eq!(hashes.len(), items.len());
+
+        for (item, &h) in items.iter().zip(&hashes) {
+            assert_eq!(h, hash64(item));
+        }
+
+        assert_eq!(hash64_batch(&[b"hello"]), [6105954949053820864]);
     }
 
     #[test]
     fn test_spooky128() {
         assert_eq!(SpookyHash128::hash(b"hello"),
-                   u128::from_parts(6105954949053820864, 16417113279381893933));
-        assert_eq!(SpookyHash128::hash_with_seed(b"hello", u128::new(123)),
-                   u128::from_parts(7262466432451564128, 15030932129358977799));
+                   (6105954949053820864_u128 << 64) | 16417113279381893933);
+        assert_eq!(SpookyHash128::hash_with_seed(b"hello", 123),
+                   (7262466432451564128_u128 << 64) | 15030932129358977799);
         assert_eq!(SpookyHash128::hash(b"helloworld"),
-                   u128::from_parts(18412934266828208920, 13883738476858207693));
+                   (18412934266828208920_u128 << 64) | 13883738476858207693);
+
+        let mut out = [0; 16];
+
+        SpookyHash128::hash_into(b"helloworld", &mut out);
+        assert_eq!(u64::from_le_bytes([out[0], out[1], out[2], out[3],
+                                       out[4], out[5], out[6], out[7]]),
+                   SpookyHash128::hash(b"helloworld") as u64);
+        assert_eq!(u64::from_le_bytes([out[8], out[9], out[10], out[11],
+                                       out[12], out[13], out[14], out[15]]),
+                   (SpookyHash128::hash(b"helloworld") >> 64) as u64);
+        assert_eq!(out, SpookyHash128::hash(b"helloworld").to_le_bytes());
+        assert_eq!(SpookyHash128::hash_array(b"helloworld"), out);
+        assert_eq!(u128::from_le_bytes(SpookyHash128::hash_array(b"hello")),
+                   hash128(b"hello"));
+
+        let mut seed = [0; 16];
+
+        seed[0] = 123;
+        assert_eq!(SpookyHash128::hash_with_seed_bytes(b"hello", &seed),
+                   SpookyHash128::hash_with_seed(b"hello", 123));
+        assert_eq!(SpookyHash128::hash_with_seed_bytes(b"hello", &seed),
+                   (7262466432451564128_u128 << 64) | 15030932129358977799);
+        assert_eq!(SpookyHash128::hash_with_seed_bytes(b"hello", &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9,
+                                                                   10, 11, 12, 13, 14, 15]),
+                   (9478365284917721830_u128 << 64) | 1144596901843936968);
 
         let mut h = SpookyHasher128::new();
 
         h.write(b"hello");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(6105954949053820864, 16417113279381893933));
+                   (6105954949053820864_u128 << 64) | 16417113279381893933);
 
         h.write(b"world");
         assert_eq!(h.finish_ext(),
-                   u128::from_parts(18412934266828208920, 13883738476858207693));
+                   (18412934266828208920_u128 << 64) | 13883738476858207693);
 
         h.write_stream(&mut Cursor::new(&[0_u8; 4567][..])).unwrap();
         assert_eq!(h.finish(), 2977683714085165920);
     }
+
+    #[test]
+    fn test_marker_derives() {
+        let markers = vec![format!("{:?}", SpookyHash32 {}),
+                           format!("{:?}", SpookyHash64::default()),
+                           format!("{:?}", SpookyHash128 {})];
+
+        assert_eq!(markers, ["SpookyHash32", "SpookyHash64", "SpookyHash128"]);
+        assert_eq!(SpookyHash64 {}, SpookyHash64::default());
+    }
+
+    #[test]
+    fn test_spooky_write_framed() {
+        let framed = |parts: &[&[u8]]| {
+            let mut h = SpookyHasher128::new();
+
+            for part in parts {
+                h.write_framed(part);
+            }
+
+            h.finish_ext()
+        };
+
+        assert!(framed(&[b"a", b"bc"]) != framed(&[b"ab", b"c"]));
+        assert_eq!(framed(&[b"hello"]), hash128(b"\x05\0\0\0\0\0\0\0hello"));
+    }
+
+    #[test]
+    fn test_spooky_extend() {
+        let data = (0..1000).map(|i| i as u8).collect::<Vec<u8>>();
+
+        let mut h = SpookyHasher128::new();
+        h.extend(b"hello".iter().copied());
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(b"hello"));
+
+        let mut h = SpookyHasher128::new();
+        h.extend(data.iter().copied());
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(&data));
+
+        let mut h = SpookyHasher128::new();
+        h.extend(data.chunks(77));
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(&data));
+    }
+
+    #[test]
+    fn test_spooky_hash_one_ext() {
+        let s = SpookyBuildHasher::with_seed((123, 456));
+
+        assert_eq!(s.hash_one_ext(&b"hello"[..]),
+                   SpookyHash128::hash_with_seed(b"\x05\0\0\0\0\0\0\0hello",
+                                                 (123_u128 << 64) | 456));
+        assert_eq!(s.hash_one_ext("hello"), s.hash_one_ext("hello"));
+        assert_eq!(s.hash_one("hello"), s.hash_one_ext("hello") as u64);
+    }
+
+    #[test]
+    fn test_hash128_parts() {
+        let h = hash128(b"hello");
+
+        assert_eq!(hash128_parts(b"hello", 0), ((h >> 64) as u64, h as u64));
+
+        let h = hash128_with_seed(b"hello", (123 << 64) | 456);
+
+        assert_eq!(hash128_parts(b"hello", (123 << 64) | 456), ((h >> 64) as u64, h as u64));
+
+        let mut hasher = SpookyHasher128::with_seed((123, 456));
+
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish_ext_parts(), ((h >> 64) as u64, h as u64));
+    }
+
+    #[test]
+    fn test_spooky_hash_vectored() {
+        let header = b"GET / HTTP/1.1\r\n";
+        let body = [0x5a_u8; 300];
+        let concat = [&header[..], &body[..]].concat();
+
+        assert_eq!(hash64_vectored(&[header, &body], 123), hash64_with_seed(&concat, 123));
+        assert_eq!(hash64_vectored(&[&concat[..7], &concat[7..200], &concat[200..]], 0),
+                   hash64(&concat));
+        assert_eq!(hash64_vectored(&[], 0), hash64(b""));
+        assert_eq!(hash64_vectored(&[b"hello"], 0), 6105954949053820864);
+        assert_eq!(hash64_vectored(&[b"hel", b"lo"], 123), hash64_with_seed(b"hello", 123));
+        assert_eq!(hash64_vectored(&[b"hello"], 123), 8819086853393477700);
+
+        assert_eq!(hash128_vectored(&[header, &body], (123 << 64) | 456),
+                   hash128_with_seed(&concat, (123 << 64) | 456));
+        assert_eq!(hash128_vectored(&[b"hel", b"", b"lo"], 0), hash128(b"hello"));
+        assert_eq!(hash128_vectored(&[b"hel", b"lo"], (123 << 64) | 456),
+                   hash128_with_seed(b"hello", (123 << 64) | 456));
+    }
+
+    #[test]
+    fn test_spooky_build_hasher() {
+        use std::collections::HashMap;
+
+        let mut map: HashMap<String, u32, SpookyBuildHasher> = HashMap::default();
+
+        map.insert("hello".to_owned(), 1);
+        assert_eq!(map.get("hello"), Some(&1));
+        assert_eq!(map.get("world"), None);
+
+        assert_eq!(SpookyBuildHasher::default().seed(), (0, 0));
+
+        let mut h = SpookyBuildHasher::with_seed((123, 456)).build_hasher();
+
+        h.write(b"hello");
+        assert_eq!(h.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"hello", (123_u128 << 64) | 456));
+
+        let s = SpookyBuildHasher::random();
+        let mut map = HashMap::with_hasher(s);
+
+        map.insert("hello".to_owned(), 1);
+        assert_eq!(map.get("hello"), Some(&1));
+    }
+
+    #[test]
+    fn test_spooky_hasher_snapshot() {
+        let data = (0..1000).map(|i| i as u8).collect::<Vec<u8>>();
+
+        for &split in &[0, 5, 191, 192, 200, 500, 1000] {
+            let mut h = SpookyHasher128::with_seed((123, 456));
+
+            h.write(&data[..split]);
+
+            let snapshot = h.snapshot();
+
+            drop(h);
+
+            let mut h = SpookyHasher128::restore(&snapshot).unwrap();
+
+            assert_eq!(h.snapshot(), snapshot);
+
+            h.write(&data[split..]);
+            assert_eq!(h.finish_ext(),
+                       SpookyHash128::hash_with_seed(&data, (123_u128 << 64) | 456));
+
+            h.reset();
+            assert_eq!(h.finish_ext(),
+                       SpookyHash128::hash_with_seed(b"", (123_u128 << 64) | 456));
+        }
+
+        // a short write after a long one buffers more than `length % 96` bytes
+        let mut h = SpookyHasher128::new();
+
+        h.write(&[1; 300]);
+        h.write(&[2; 100]);
+
+        let restored = SpookyHasher128::restore(&h.snapshot()).unwrap();
+
+        assert_eq!(restored.finish_ext(), h.finish_ext());
+    }
+
+    #[test]
+    fn test_spooky_hasher_restore_invalid() {
+        use std::mem;
+
+        let mut h = SpookyHasher128::new();
+
+        h.write(b"hello");
+
+        let snapshot = h.snapshot();
+
+        assert_eq!(SpookyHasher128::restore(b"").err(), Some(InvalidState));
+        assert_eq!(SpookyHasher128::restore(&snapshot[1..]).err(), Some(InvalidState));
+
+        // a total length of 5 bytes must have all of them buffered
+        let mut corrupted = snapshot.clone();
+        let remainder = 36 * 8 + mem::size_of::<usize>();
+
+        assert_eq!(corrupted[remainder], 5);
+        corrupted[remainder] = 200;
+        assert_eq!(SpookyHasher128::restore(&corrupted).err(), Some(InvalidState));
+
+        // the buffer never holds two whole blocks
+        let mut h = SpookyHasher128::new();
+
+        h.write(&[1; 300]);
+
+        let mut corrupted = h.snapshot();
+
+        corrupted[remainder] = 192;
+        assert_eq!(SpookyHasher128::restore(&corrupted).err(), Some(InvalidState));
+        corrupted[remainder] = 191;
+        assert!(SpookyHasher128::restore(&corrupted).is_ok());
+    }
+
+    #[test]
+    fn test_spooky_hasher_by_mut_ref() {
+        fn hash_each<H: Hasher>(mut state: H, values: &[u32]) -> u64 {
+            for v in values {
+                v.hash(&mut state);
+            }
+
+            state.finish()
+        }
+
+        let values: Vec<u32> = vec![1, 2, 3];
+        let bytes = values.iter().flat_map(|v| v.to_ne_bytes()).collect::<Vec<u8>>();
+        let mut h = SpookyHasher128::new();
+
+        assert_eq!(hash_each(&mut h, &values), SpookyHash128::hash(&bytes) as u64);
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(&bytes));
+
+        let mut h = SpookyHasher128::new();
+        let state = &mut h;
+
+        for v in &values {
+            v.hash(state);
+        }
+
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(&bytes));
+    }
+
+    #[test]
+    fn test_spooky_build_hasher_default() {
+        use std::collections::HashMap;
+        use std::hash::BuildHasherDefault;
+
+        let mut map: HashMap<&str, u32, BuildHasherDefault<SpookyHasher128>> =
+            HashMap::with_hasher(BuildHasherDefault::<SpookyHasher128>::default());
+
+        map.insert("hello", 1);
+        assert_eq!(map.get("hello"), Some(&1));
+        assert_eq!(map.get("world"), None);
+
+        let mut h = SpookyHasher128::default();
+
+        h.write(b"hello");
+        assert_eq!(h.finish(), 16417113279381893933);
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(b"hello"));
+    }
+
+    #[test]
+    fn test_log_hasher() {
+        let mut log = LogHasher::new();
+        let mut prefix = Vec::new();
+
+        assert!(log.is_empty());
+        assert_eq!(log.hash(), SpookyHash128::hash(b""));
+
+        for i in 0..1000 {
+            let record = format!("record #{}\n", i).repeat(i % 7);
+
+            prefix.extend_from_slice(record.as_bytes());
+
+            assert_eq!(log.append(record.as_bytes()), SpookyHash128::hash(&prefix));
+        }
+
+        assert_eq!(log.len(), prefix.len());
+        assert_eq!(log.hash(), SpookyHash128::hash(&prefix));
+    }
+
+    #[test]
+    fn test_hash64_typed() {
+        let h = hash64_typed(b"hello");
+
+        assert_eq!(h, SpookyHash64Value(6105954949053820864));
+        assert_eq!(*h, SpookyHash64::hash(b"hello"));
+        assert!(h != hash64_typed(b"world"));
+    }
+
+    #[test]
+    fn test_spooky_reset() {
+        let mut h = SpookyHasher128::new();
+
+        h.write(b"hello");
+        h.reset();
+        h.write(b"world");
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(b"world"));
+
+        let mut h = SpookyHasher128::with_seed((123, 456));
+
+        h.write(b"hello");
+        h.reset();
+        h.write(b"world");
+        assert_eq!(h.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"world", (123_u128 << 64) | 456));
+
+        h.reset_with_seed(123);
+        h.write(b"world");
+        assert_eq!(h.finish(),
+                   SpookyHash128::hash_with_seed(b"world", (123_u128 << 64) | 123) as u64);
+        assert_eq!(h.finish_ext() >> 64, SpookyHash64::hash_with_seed(b"world", 123) as u128);
+
+        h.reset_with_seed128((456_u128 << 64) | 789);
+        h.write(b"world");
+        assert_eq!(h.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"world", (456_u128 << 64) | 789));
+
+        h.write(b"hello");
+        h.reset();
+        h.write(b"world");
+        assert_eq!(h.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"world", (456_u128 << 64) | 789));
+    }
+
+    #[test]
+    fn test_spooky_finish32() {
+        let mut h = SpookyHasher128::new();
+
+        h.write(b"he");
+        h.write(b"llo");
+        assert_eq!(h.finish32(), SpookyHash32::hash(b"hello"));
+        assert_eq!(h.finish32(), 3907268544);
+
+        let mut h = SpookyHasher128::with_seed((123, 123));
+
+        h.write(b"hello");
+        assert_eq!(h.finish32(), SpookyHash32::hash_with_seed(b"hello", 123));
+    }
+
+    #[test]
+    fn test_spooky_finish_truncated() {
+        let mut h = SpookyHasher128::with_seed((123, 456));
+
+        h.write(b"hello");
+        assert_eq!(h.finish(), h.finish_ext() as u64);
+        assert_eq!(h.finish_truncated(), h.finish());
+        assert_eq!(h.finish_truncated(), h.finish_ext_parts().1);
+        assert_eq!(SpookyHasher128::new().finish(), hash128(b"") as u64);
+    }
+
+    #[test]
+    fn test_spooky_finish64() {
+        let data = (0..1000).map(|i| (i * 7 + 3) as u8).collect::<Vec<u8>>();
+
+        for &len in &[0, 1, 5, 15, 16, 31, 32, 95, 96, 191, 192, 193, 1000] {
+            for &seed in &[0, 123, u64::MAX, 0x5ec9e7_u64 << 32] {
+                let mut h = SpookyHasher128::with_seed((seed, seed));
+
+                h.write(&data[..len / 2]);
+                h.write(&data[len / 2..len]);
+                assert_eq!(h.finish64(), SpookyHash64::hash_with_seed(&data[..len], seed));
+                assert_eq!(h.finish(), h.finish_ext() as u64);
+            }
+        }
+
+        let mut h = SpookyHasher128::with_seed((123, 123));
+
+        h.write(b"hello");
+        assert_eq!(h.finish64(), 8819086853393477700);
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn test_spooky_digest() {
+        use digest::Digest;
+
+        assert_eq!(SpookyDigest::digest(b"hello")[..], hash128(b"hello").to_le_bytes()[..]);
+
+        let mut d = SpookyDigest::with_seed((123, 456));
+
+        Digest::update(&mut d, b"hel");
+        Digest::update(&mut d, b"lo");
+        assert_eq!(d.finalize_reset()[..],
+                   hash128_with_seed(b"hello", (123 << 64) | 456).to_le_bytes()[..]);
+
+        Digest::update(&mut d, b"world");
+        assert_eq!(d.finalize()[..],
+                   hash128_with_seed(b"world", (123 << 64) | 456).to_le_bytes()[..]);
+    }
+
+    #[test]
+    fn test_spooky_try_with_seed() {
+        let mut h = SpookyHasher128::try_with_seed((123, 456)).unwrap();
+
+        h.write(b"hello");
+        assert_eq!(h.finish_ext(), SpookyHash128::hash_with_seed(b"hello", (123 << 64) | 456));
+
+        let mut h = SpookyHasher128::try_new().unwrap();
+
+        h.write(b"hello");
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(b"hello"));
+        assert_eq!(AllocError.to_string(), "failed to allocate the SpookyHash state");
+    }
+
+    #[test]
+    fn test_spooky_clone() {
+        let mut h1 = SpookyHasher128::with_seed((123, 456));
+
+        h1.write(b"hello");
+
+        let mut h2 = h1.clone();
+
+        h1.write(b"world");
+        h2.write(b" world");
+
+        assert_eq!(h1.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"helloworld", (123_u128 << 64) | 456));
+        assert_eq!(h2.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"hello world", (123_u128 << 64) | 456));
+
+        let mut h3 = h2.clone();
+
+        drop(h2);
+
+        h3.reset();
+        h3.write(b"world");
+        assert_eq!(h3.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"world", (123_u128 << 64) | 456));
+    }
+
+    #[test]
+    fn test_spooky_hasher_await_safe() {
+        fn assert_await_safe<T: Unpin + Send>() {}
+
+        assert_await_safe::<SpookyHasher128>();
+        assert_await_safe::<::farm::FarmHasher64>();
+    }
+
+    #[test]
+    fn test_spooky_hasher_send() {
+        let mut h = SpookyHasher128::with_seed((123, 456));
+
+        h.write(b"hello");
+
+        let h = thread::spawn(move || {
+                h.write(b" world");
+                h.finish_ext()
+            })
+            .join()
+            .unwrap();
+
+        assert_eq!(h, hash128_with_seed(b"hello world", (123 << 64) | 456));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_spooky_zeroize_drop() {
+        // only a smoke test of the `zeroize` drop path: it neither checks that the state
+        // was zeroed nor that it was freed, which would need a leak checker
+        let mut h = SpookyHasher128::with_seed((123, 0x5ec9e7));
+
+        h.write(b"hello");
+
+        let h2 = h.clone();
+
+        drop(h);
+        assert_eq!(h2.finish_ext(),
+                   SpookyHash128::hash_with_seed(b"hello", (123 << 64) | 0x5ec9e7));
+    }
+
+    #[test]
+    fn test_spooky_io_write() {
+        let data = (0..10_000).map(|i| (i * 7) as u8).collect::<Vec<u8>>();
+        let mut h = SpookyHasher128::new();
+
+        assert_eq!(io::copy(&mut Cursor::new(&data), &mut h).unwrap(),
+                   data.len() as u64);
+        assert_eq!(h.finish_ext(), SpookyHash128::hash(&data));
+        assert_eq!((h.finish_ext() >> 64) as u64, SpookyHash64::hash(&data));
+        assert_eq!(h.finish(), SpookyHash128::hash(&data) as u64);
+    }
 }
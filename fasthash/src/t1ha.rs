@@ -46,11 +46,13 @@
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
-use std::os::raw::c_void;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
 
 /// `T1Hash` 64-bit hash functions for 64-bit little-endian platforms.
 pub struct T1ha64Le {}
@@ -60,7 +62,7 @@ impl FastHash for T1ha64Le {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::t1ha(bytes.as_ref().as_ptr() as *const c_void,
                       bytes.as_ref().len(),
@@ -79,7 +81,7 @@ impl FastHash for T1ha64Be {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::t1ha_64be(bytes.as_ref().as_ptr() as *const c_void,
                            bytes.as_ref().len(),
@@ -98,7 +100,7 @@ impl FastHash for T1ha32Le {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::t1ha_32le(bytes.as_ref().as_ptr() as *const c_void,
                            bytes.as_ref().len(),
@@ -117,7 +119,7 @@ impl FastHash for T1ha32Be {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::t1ha_32be(bytes.as_ref().as_ptr() as *const c_void,
                            bytes.as_ref().len(),
@@ -138,7 +140,7 @@ impl FastHash for T1ha64Crc {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::t1ha_ia32crc(bytes.as_ref().as_ptr() as *const c_void,
                               bytes.as_ref().len(),
@@ -152,21 +154,21 @@ impl_hasher!(T1ha64CrcHasher, T1ha64Crc);
 
 /// `T1Hash` 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u64 {
     T1ha32Le::hash(v)
 }
 
 /// `T1Hash` 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     T1ha32Le::hash_with_seed(v, seed)
 }
 
 /// `T1Hash` 64-bit hash functions for a byte array.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     T1ha64Le::hash(v)
 }
 
@@ -174,7 +176,7 @@ pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[cfg(not(feature = "sse42"))]
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     T1ha64Le::hash_with_seed(v, seed)
 }
 
@@ -182,7 +184,7 @@ pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
 /// That require SSE4.2 instructions to be available.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     T1ha64Crc::hash(v)
 }
 
@@ -191,10 +193,19 @@ pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[cfg(any(feature = "doc", feature = "sse42"))]
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     T1ha64Crc::hash_with_seed(v, seed)
 }
 
+/// `T1Hash` 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use std::hash::Hasher;
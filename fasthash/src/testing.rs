@@ -0,0 +1,85 @@
+//! Helpers to characterize the hash functions.
+//!
+//! # Example
+//!
+//! ```
+//! use fasthash::farm::FarmHash64;
+//! use fasthash::testing::latency_ns;
+//!
+//! let stats = latency_ns::<FarmHash64>(b"hello world", 1000);
+//!
+//! assert!(stats.min <= stats.median);
+//! assert!(stats.median <= stats.p99);
+//! ```
+//!
+use std::hint::black_box;
+use std::time::Instant;
+
+use hasher::FastHash;
+
+/// Latency distribution of a hash function, in nanoseconds per call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatencyStats {
+    /// The fastest observed call.
+    pub min: f64,
+    /// The median call.
+    pub median: f64,
+    /// The 99th percentile call.
+    pub p99: f64,
+}
+
+/// Measure the latency distribution of `H::hash` over `input`.
+///
+/// Each of the `samples` calls is timed individually, so the result includes
+/// the timer overhead; it is intended to characterize the tail latency
+/// (e.g. FFI call jitter) rather than the throughput.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero.
+pub fn latency_ns<H: FastHash<Value = u64>>(input: &[u8], samples: usize) -> LatencyStats {
+    assert!(samples > 0, "at least one sample is required");
+
+    let mut timings = Vec::with_capacity(samples);
+
+    for _ in 0..samples {
+        let start = Instant::now();
+        black_box(H::hash(black_box(&input)));
+        timings.push(start.elapsed().as_nanos() as f64);
+    }
+
+    timings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    LatencyStats {
+        min: timings[0],
+        median: percentile(&timings, 0.5),
+        p99: percentile(&timings, 0.99),
+    }
+}
+
+#[inline]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    sorted[((sorted.len() - 1) as f64 * p).round() as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use farm::FarmHash64;
+    use super::*;
+
+    #[test]
+    fn test_latency_ns() {
+        let stats = latency_ns::<FarmHash64>(b"hello world", 1000);
+
+        assert!(stats.min.is_finite());
+        assert!(stats.median.is_finite());
+        assert!(stats.p99.is_finite());
+        assert!(stats.min <= stats.median);
+        assert!(stats.median <= stats.p99);
+
+        let stats = latency_ns::<FarmHash64>(b"", 1);
+
+        assert_eq!(stats.min, stats.median);
+        assert_eq!(stats.median, stats.p99);
+    }
+}
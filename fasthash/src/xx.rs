@@ -28,12 +28,17 @@
 //! assert_eq!(h, hash(&"hello world"));
 //! ```
 //!
+#[cfg(feature = "alloc")]
 use std::hash::Hasher;
-use std::os::raw::c_void;
+use std::ffi::c_void;
 
 use ffi;
 
-use hasher::{FastHash, FastHasher, StreamHasher};
+use hasher::FastHash;
+#[cfg(feature = "alloc")]
+use hasher::FastHasher;
+#[cfg(feature = "std")]
+use hasher::StreamHasher;
 
 /// xxHash 32-bit hash functions
 pub struct XXHash32 {}
@@ -43,7 +48,7 @@ impl FastHash for XXHash32 {
     type Seed = u32;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u32) -> u32 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u32) -> u32 {
         unsafe {
             ffi::XXH32(bytes.as_ref().as_ptr() as *const c_void,
                        bytes.as_ref().len(),
@@ -60,7 +65,7 @@ impl FastHash for XXHash64 {
     type Seed = u64;
 
     #[inline]
-    fn hash_with_seed<T: AsRef<[u8]>>(bytes: &T, seed: u64) -> u64 {
+    fn hash_with_seed<T: AsRef<[u8]>>(bytes: T, seed: u64) -> u64 {
         unsafe {
             ffi::XXH64(bytes.as_ref().as_ptr() as *const c_void,
                        bytes.as_ref().len(),
@@ -71,39 +76,51 @@ impl FastHash for XXHash64 {
 
 /// xxHash 32-bit hash functions for a byte array.
 #[inline]
-pub fn hash32<T: AsRef<[u8]>>(v: &T) -> u32 {
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
     XXHash32::hash(v)
 }
 
 /// xxHash 32-bit hash function for a byte array.
 /// For convenience, a 32-bit seed is also hashed into the result.
 #[inline]
-pub fn hash32_with_seed<T: AsRef<[u8]>>(v: &T, seed: u32) -> u32 {
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     XXHash32::hash_with_seed(v, seed)
 }
 
 /// xxHash 64-bit hash functions for a byte array.
 #[inline]
-pub fn hash64<T: AsRef<[u8]>>(v: &T) -> u64 {
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
     XXHash64::hash(v)
 }
 
 /// xxHash 64-bit hash function for a byte array.
 /// For convenience, a 64-bit seed is also hashed into the result.
 #[inline]
-pub fn hash64_with_seed<T: AsRef<[u8]>>(v: &T, seed: u64) -> u64 {
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
     XXHash64::hash_with_seed(v, seed)
 }
 
+/// xxHash 64-bit hash function for a UTF-8 string.
+///
+/// Equivalent to `hash64(s.as_bytes())`: only the UTF-8 bytes of the string are hashed,
+/// unlike the `Hash` implementation of `str` which also writes a trailing `0xff` byte.
+#[inline]
+pub fn hash64_str(s: &str) -> u64 {
+    hash64(s.as_bytes())
+}
+
 /// An implementation of `std::hash::Hasher`.
+#[cfg(feature = "alloc")]
 pub struct XXHasher32(*mut ffi::XXH32_state_t);
 
+#[cfg(feature = "alloc")]
 impl Default for XXHasher32 {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Drop for XXHasher32 {
     #[inline]
     fn drop(&mut self) {
@@ -113,6 +130,7 @@ impl Drop for XXHasher32 {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Hasher for XXHasher32 {
     #[inline]
     fn finish(&self) -> u64 {
@@ -129,6 +147,7 @@ impl Hasher for XXHasher32 {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl FastHasher for XXHasher32 {
     type Seed = u32;
 
@@ -144,19 +163,23 @@ impl FastHasher for XXHasher32 {
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for XXHasher32 {}
 
 impl_fasthash!(XXHasher32, XXHash32);
 
 /// An implementation of `std::hash::Hasher`.
+#[cfg(feature = "alloc")]
 pub struct XXHasher64(*mut ffi::XXH64_state_t);
 
+#[cfg(feature = "alloc")]
 impl Default for XXHasher64 {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Drop for XXHasher64 {
     fn drop(&mut self) {
         unsafe {
@@ -165,6 +188,7 @@ impl Drop for XXHasher64 {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Hasher for XXHasher64 {
     #[inline]
     fn finish(&self) -> u64 {
@@ -181,6 +205,7 @@ impl Hasher for XXHasher64 {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl FastHasher for XXHasher64 {
     type Seed = u64;
 
@@ -196,6 +221,7 @@ impl FastHasher for XXHasher64 {
     }
 }
 
+#[cfg(feature = "std")]
 impl StreamHasher for XXHasher64 {}
 
 impl_fasthash!(XXHasher64, XXHash64);
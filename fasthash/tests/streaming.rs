@@ -0,0 +1,61 @@
+//! The streaming hashers must produce the one-shot hash of the concatenated input,
+//! wherever the input is split into writes.
+
+extern crate proptest;
+extern crate fasthash;
+
+use std::hash::Hasher;
+
+use proptest::prelude::*;
+
+use fasthash::{farm, spooky, FastHasher, HasherExt};
+use fasthash::farm::{FarmHasher64, FarmHasher128};
+use fasthash::spooky::SpookyHasher128;
+
+/// Splits the data at the split points, taken modulo `data.len() + 1`.
+fn chunks(data: &[u8], splits: &[usize]) -> Vec<Vec<u8>> {
+    let mut points = splits.iter().map(|&p| p % (data.len() + 1)).collect::<Vec<_>>();
+
+    points.push(0);
+    points.push(data.len());
+    points.sort();
+
+    points.windows(2).map(|w| data[w[0]..w[1]].to_vec()).collect()
+}
+
+proptest! {
+    // Cover several of SpookyHash's 96-byte blocks and its 192-byte short input limit.
+    #[test]
+    fn spooky_streaming_matches_oneshot(data in prop::collection::vec(any::<u8>(), 0..1024),
+                                        splits in prop::collection::vec(any::<usize>(), 0..8),
+                                        seed in any::<(u64, u64)>()) {
+        let mut h = SpookyHasher128::with_seed(seed);
+
+        for chunk in chunks(&data, &splits) {
+            h.write(&chunk);
+        }
+
+        let oneshot = spooky::hash128_with_seed(&data, ((seed.0 as u128) << 64) | seed.1 as u128);
+
+        prop_assert_eq!(h.finish_ext(), oneshot);
+        prop_assert_eq!(h.finish(), oneshot as u64);
+    }
+
+    // `FarmHash` has no streaming API, the hashers buffer all the writes,
+    // so this only checks the buffering.
+    #[test]
+    fn farm_buffered_matches_oneshot(data in prop::collection::vec(any::<u8>(), 0..1024),
+                                     splits in prop::collection::vec(any::<usize>(), 0..8),
+                                     seed in any::<u64>()) {
+        let mut h64 = FarmHasher64::with_seed(seed);
+        let mut h128 = FarmHasher128::new();
+
+        for chunk in chunks(&data, &splits) {
+            h64.write(&chunk);
+            h128.write(&chunk);
+        }
+
+        prop_assert_eq!(h64.finish(), farm::hash64_with_seed(&data, seed));
+        prop_assert_eq!(h128.finish_ext(), farm::hash128(&data));
+    }
+}